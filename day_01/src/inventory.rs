@@ -0,0 +1,50 @@
+use std::str::FromStr;
+
+use crate::stream::ElfReader;
+
+/// An elf and the calories of every snack it carries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Elf {
+    pub items: Vec<u32>,
+}
+
+/// Every elf of the expedition, in input order
+#[derive(Debug, Clone, Default)]
+pub struct Inventory {
+    pub elves: Vec<Elf>,
+}
+
+impl Elf {
+    /// Sum of the snacks, which can't overflow even for huge synthetic inventories
+    pub fn total(&self) -> u64 {
+        self.items.iter().copied().map(u64::from).sum()
+    }
+}
+
+impl Inventory {
+    pub fn totals(&self) -> Vec<u64> {
+        self.elves.iter().map(Elf::total).collect()
+    }
+}
+
+/// Parses a single inventory line, blank lines being group separators (`None`).
+///
+/// `line_number` is 1-based and only used in the error message
+pub fn parse_line(line_number: usize, line: &str) -> Result<Option<u32>, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    line.parse()
+        .map(Some)
+        .map_err(|e| format!("line {line_number}: `{line}` is not a valid calorie count ({e})"))
+}
+
+impl FromStr for Inventory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let elves = ElfReader::new(s.as_bytes()).collect::<Result<_, _>>()?;
+        Ok(Self { elves })
+    }
+}
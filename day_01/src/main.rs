@@ -1,21 +1,205 @@
-fn main() {
-    let file = include_str!("../input.txt");
-    let mut values = vec![0];
-    let mut i = 0;
-
-    for line in file.lines() {
-        if line.is_empty() {
-            values.push(0);
-            i += 1;
-        } else {
-            let num: u32 = line.parse().unwrap();
-            values[i] += num;
+mod inventory;
+mod partition;
+mod ranking;
+mod redistribution;
+mod stats;
+mod stream;
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    str::FromStr,
+};
+
+use inventory::Inventory;
+use partition::partition;
+use ranking::{bottom_n, rank_of, top_n, Order, Ranking};
+use redistribution::{plan, validate};
+use stats::{Format, Report, Statistics};
+use stream::ElfReader;
+
+/// Command line options:
+/// `day_01 [--stats] [--csv] [--percentiles 25,75] [--buckets 10] [--teams 3]
+/// [--members] [--redistribute <tolerance>] [<path> | -]`
+#[derive(Debug)]
+struct Options {
+    input: Option<String>,
+    /// Prints the statistics report, in CSV with `--csv`
+    stats: bool,
+    format: Format,
+    percentiles: Vec<f64>,
+    buckets: usize,
+    teams: Option<usize>,
+    /// Lists the (1-based) elves of every team
+    members: bool,
+    redistribute: Option<u32>,
+}
+
+impl Options {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            input: None,
+            stats: false,
+            format: Format::Table,
+            percentiles: vec![25.0, 75.0, 90.0],
+            buckets: 10,
+            teams: None,
+            members: false,
+            redistribute: None,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("Missing `{arg}` value"));
+            match arg.as_str() {
+                "--stats" => options.stats = true,
+                "--csv" => {
+                    options.stats = true;
+                    options.format = Format::Csv;
+                }
+                "--percentiles" => {
+                    options.percentiles = value()?
+                        .split(',')
+                        .map(|p| {
+                            p.parse()
+                                .map_err(|_| format!("{p} is not a valid percentile"))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "--buckets" => {
+                    let v = value()?;
+                    options.buckets = v
+                        .parse()
+                        .map_err(|_| format!("{v} is not a valid bucket count"))?;
+                }
+                "--teams" => {
+                    let v = value()?;
+                    let teams = v
+                        .parse()
+                        .map_err(|_| format!("{v} is not a valid team count"))?;
+                    options.teams = Some(teams);
+                }
+                "--members" => options.members = true,
+                "--redistribute" => {
+                    let v = value()?;
+                    let tolerance = v
+                        .parse()
+                        .map_err(|_| format!("{v} is not a valid calorie tolerance"))?;
+                    options.redistribute = Some(tolerance);
+                }
+                _ if options.input.is_none() => options.input = Some(arg),
+                _ => return Err(format!("Unexpected argument `{arg}`")),
+            }
         }
+        Ok(options)
+    }
+}
+
+/// Aggregates an inventory of any size, printing every new calorie leader as groups complete
+fn stream(reader: impl BufRead) -> Result<(), String> {
+    let mut ranking = Ranking::new(Order::Highest, 3);
+    let mut max = None;
+    for (index, elf) in ElfReader::new(reader).enumerate() {
+        let calories = elf?.total();
+        ranking.push(index, calories);
+        if max < Some(calories) {
+            println!("Elf #{index}: new max value = {calories}");
+            max = Some(calories);
+        }
+    }
+    let top = ranking.into_sorted_vec();
+    let total: u64 = top.iter().map(|ranked| ranked.calories).sum();
+    println!("Part 1: Max value = {}", max.unwrap_or_default());
+    println!("Part 2: Total = {total} ");
+    Ok(())
+}
+
+fn main() {
+    let options = Options::from_args(env::args().skip(1)).unwrap();
+    match options.input.as_deref() {
+        Some("-") => return stream(io::stdin().lock()).unwrap(),
+        Some(path) => return stream(BufReader::new(File::open(path).unwrap())).unwrap(),
+        None => (),
     }
+    let inventory = Inventory::from_str(include_str!("../input.txt")).unwrap();
+    let values = inventory.totals();
 
-    values.sort_unstable();
-    values.reverse();
-    let [a, b, c] = [values[0], values[1], values[2]];
-    println!("Part 1: Max value = {}", a);
-    println!("Part 2: Total = {} ", a + b + c);
+    let report = options.stats.then(|| Report {
+        series: [
+            ("calories", values.clone()),
+            (
+                "items",
+                inventory
+                    .elves
+                    .iter()
+                    .map(|e| e.items.len() as u64)
+                    .collect(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, v)| Some((name.to_string(), Statistics::new(v)?)))
+        .collect(),
+        percentiles: options.percentiles,
+        buckets: options.buckets,
+    });
+    if let (Some(report), Format::Csv) = (&report, options.format) {
+        print!("{}", report.render(Format::Csv));
+        return;
+    }
+
+    let top = top_n(&values, 3);
+    println!("Part 1: Max value = {}", top[0].calories);
+    let total: u64 = top.iter().map(|ranked| ranked.calories).sum();
+    println!("Part 2: Total = {total} ");
+    if let Some(report) = report {
+        let bottom = bottom_n(&values, values.len() / 4);
+        if let Some(last) = bottom.last() {
+            let rank = rank_of(&values, last.elf).unwrap();
+            println!(
+                "Bottom quartile: {} elves carrying at most {} calories (rank {rank})",
+                bottom.len(),
+                last.calories
+            );
+        }
+        print!("{}", report.render(Format::Table));
+    }
+    if let Some(teams) = options.teams {
+        let partition = partition(&values, teams);
+        println!(
+            "Teams: {} teams with a spread of {} calories",
+            partition.teams.len(),
+            partition.spread()
+        );
+        for (i, team) in partition.teams.iter().enumerate() {
+            println!(
+                "Team {}: {} elves carrying {} calories",
+                i + 1,
+                team.elves.len(),
+                team.calories
+            );
+            if options.members {
+                let mut elves: Vec<_> = team.elves.iter().map(|elf| elf + 1).collect();
+                elves.sort_unstable();
+                let elves: Vec<_> = elves.iter().map(ToString::to_string).collect();
+                println!("  elves {}", elves.join(", "));
+            }
+        }
+    }
+    if let Some(tolerance) = options.redistribute {
+        let plan = plan(&inventory, tolerance);
+        let (transfers, result) = match plan.and_then(|transfers| {
+            let result = validate(&inventory, &transfers, tolerance)?;
+            Ok((transfers, result))
+        }) {
+            Ok(res) => res,
+            Err(e) => return println!("Redistribution: {e}"),
+        };
+        println!("Redistribution: {} transfers", transfers.len());
+        for transfer in &transfers {
+            println!(
+                "Elf #{} gives {} calories to elf #{}",
+                transfer.from, transfer.calories, transfer.to
+            );
+        }
+        println!("Resulting totals: {:?}", result.totals());
+    }
 }
@@ -0,0 +1,151 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+/// Up to this amount of elves, [`partition`] searches for the optimal assignment
+const EXACT_LIMIT: usize = 12;
+
+/// A group of elves and their combined calories
+#[derive(Debug, Clone, Default)]
+pub struct Team {
+    pub elves: Vec<usize>,
+    pub calories: u64,
+}
+
+/// Elves split into teams
+#[derive(Debug, Clone)]
+pub struct Partition {
+    pub teams: Vec<Team>,
+}
+
+/// Karmarkar-Karp partial solution, ordered by spread
+#[derive(Debug)]
+struct Candidate(Vec<Team>);
+
+impl Team {
+    fn push(&mut self, elf: usize, calories: u64) {
+        self.elves.push(elf);
+        self.calories += calories;
+    }
+
+    fn pop(&mut self, calories: u64) {
+        self.elves.pop();
+        self.calories -= calories;
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        self.elves.extend(other.elves);
+        self.calories += other.calories;
+        self
+    }
+}
+
+impl Partition {
+    /// Difference between the most and the least supplied teams
+    pub fn spread(&self) -> u64 {
+        spread(&self.teams)
+    }
+}
+
+fn spread(teams: &[Team]) -> u64 {
+    let calories = teams.iter().map(|t| t.calories);
+    calories.clone().max().unwrap_or(0) - calories.min().unwrap_or(0)
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        spread(&self.0).cmp(&spread(&other.0))
+    }
+}
+
+/// Splits the elves into `k` teams with calorie totals as even as possible.
+///
+/// Small inputs are solved exactly, larger ones with the Karmarkar-Karp differencing heuristic.
+pub fn partition(totals: &[u64], k: usize) -> Partition {
+    let k = k.max(1);
+    let teams = if totals.len() <= EXACT_LIMIT {
+        exact(totals, k)
+    } else {
+        karmarkar_karp(totals, k)
+    };
+    Partition { teams }
+}
+
+fn exact(totals: &[u64], k: usize) -> Vec<Team> {
+    fn search(
+        elves: &[(usize, u64)],
+        remaining: u64,
+        teams: &mut Vec<Team>,
+        best: &mut Option<Vec<Team>>,
+    ) {
+        let Some((&(elf, calories), rest)) = elves.split_first() else {
+            if best.as_ref().is_none_or(|b| spread(teams) < spread(b)) {
+                *best = Some(teams.clone());
+            }
+            return;
+        };
+        let max = teams.iter().map(|t| t.calories).max().unwrap_or(0);
+        let min = teams.iter().map(|t| t.calories).min().unwrap_or(0);
+        // The least supplied team can at best receive every remaining elf
+        let lower_bound = max.saturating_sub(min + remaining);
+        if best.as_ref().is_some_and(|b| lower_bound >= spread(b)) {
+            return;
+        }
+        for i in 0..teams.len() {
+            // Empty teams are interchangeable, only try the first one
+            if teams[i].elves.is_empty() && teams[..i].iter().any(|t| t.elves.is_empty()) {
+                break;
+            }
+            teams[i].push(elf, calories);
+            search(rest, remaining - calories, teams, best);
+            teams[i].pop(calories);
+        }
+    }
+
+    let mut elves: Vec<_> = totals.iter().copied().enumerate().collect();
+    elves.sort_unstable_by_key(|&(_, calories)| Reverse(calories));
+    let remaining = totals.iter().sum();
+    let mut best = None;
+    search(&elves, remaining, &mut vec![Team::default(); k], &mut best);
+    best.unwrap_or_default()
+}
+
+fn karmarkar_karp(totals: &[u64], k: usize) -> Vec<Team> {
+    let mut heap: BinaryHeap<_> = totals
+        .iter()
+        .enumerate()
+        .map(|(elf, &calories)| {
+            let mut teams = vec![Team::default(); k];
+            teams[0].push(elf, calories);
+            Candidate(teams)
+        })
+        .collect();
+    while heap.len() > 1 {
+        let (Candidate(a), Candidate(b)) = (heap.pop().unwrap(), heap.pop().unwrap());
+        // Teams are sorted by decreasing calories, so the richest of `a` gets the poorest of `b`
+        let mut teams: Vec<_> = a
+            .into_iter()
+            .zip(b.into_iter().rev())
+            .map(|(a, b)| a.merge(b))
+            .collect();
+        teams.sort_unstable_by_key(|team| Reverse(team.calories));
+        heap.push(Candidate(teams));
+    }
+    heap.pop()
+        .map_or_else(|| vec![Team::default(); k], |Candidate(teams)| teams)
+}
@@ -0,0 +1,107 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// Which end of the calorie scale a [`Ranking`] keeps
+#[derive(Debug, Clone, Copy)]
+pub enum Order {
+    Highest,
+    Lowest,
+}
+
+/// An elf and its calorie total, as stored in a [`Ranking`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ranked {
+    pub elf: usize,
+    pub calories: u64,
+}
+
+/// Bounded ranking keeping only the `capacity` best elves for its [`Order`].
+///
+/// Elves with the same total are ranked by index, the first elf coming first.
+#[derive(Debug, Clone)]
+pub struct Ranking {
+    order: Order,
+    capacity: usize,
+    // Min-heap on `(key, Reverse(elf))`, so the worst kept elf is always on top
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+}
+
+impl Order {
+    /// Maps calories to a key where greater is always better.
+    /// The mapping is its own inverse.
+    const fn key(self, calories: u64) -> u64 {
+        match self {
+            Self::Highest => calories,
+            Self::Lowest => !calories,
+        }
+    }
+}
+
+impl Ranking {
+    pub fn new(order: Order, capacity: usize) -> Self {
+        Self {
+            order,
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity + 1),
+        }
+    }
+
+    pub fn push(&mut self, elf: usize, calories: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let entry = (self.order.key(calories), Reverse(elf));
+        if self.heap.len() < self.capacity {
+            self.heap.push(Reverse(entry));
+        } else if self
+            .heap
+            .peek()
+            .is_some_and(|Reverse(worst)| entry > *worst)
+        {
+            self.heap.pop();
+            self.heap.push(Reverse(entry));
+        }
+    }
+
+    /// Returns the kept elves, best first
+    pub fn into_sorted_vec(self) -> Vec<Ranked> {
+        let order = self.order;
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((key, Reverse(elf)))| Ranked {
+                elf,
+                calories: order.key(key),
+            })
+            .collect()
+    }
+}
+
+impl Extend<(usize, u64)> for Ranking {
+    fn extend<T: IntoIterator<Item = (usize, u64)>>(&mut self, iter: T) {
+        for (elf, calories) in iter {
+            self.push(elf, calories);
+        }
+    }
+}
+
+/// Returns the `n` elves carrying the most calories, best first
+pub fn top_n(totals: &[u64], n: usize) -> Vec<Ranked> {
+    let mut ranking = Ranking::new(Order::Highest, n);
+    ranking.extend(totals.iter().copied().enumerate());
+    ranking.into_sorted_vec()
+}
+
+/// Returns the `n` elves carrying the fewest calories, lightest first
+pub fn bottom_n(totals: &[u64], n: usize) -> Vec<Ranked> {
+    let mut ranking = Ranking::new(Order::Lowest, n);
+    ranking.extend(totals.iter().copied().enumerate());
+    ranking.into_sorted_vec()
+}
+
+/// Returns the 1-based rank of `elf`, the highest total being ranked 1.
+///
+/// Tied elves share the same rank and the next rank is skipped (`1, 2, 2, 4`).
+pub fn rank_of(totals: &[u64], elf: usize) -> Option<usize> {
+    let calories = *totals.get(elf)?;
+    Some(totals.iter().filter(|&&c| c > calories).count() + 1)
+}
@@ -0,0 +1,317 @@
+use std::cmp::Reverse;
+
+use crate::inventory::Inventory;
+
+/// Up to this amount of snacks, [`plan`] searches for the fewest transfers
+const EXACT_LIMIT: usize = 12;
+
+/// A whole snack given by an elf to another one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: usize,
+    pub to: usize,
+    pub calories: u32,
+}
+
+/// Calorie range every elf must end up in
+#[derive(Debug, Clone, Copy)]
+struct Band {
+    average: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Band {
+    fn new(inventory: &Inventory, tolerance: u32) -> Self {
+        let total: f64 = inventory.totals().into_iter().map(|c| c as f64).sum();
+        let average = total / inventory.elves.len().max(1) as f64;
+        let tolerance = f64::from(tolerance);
+        Self {
+            average,
+            min: average - tolerance,
+            max: average + tolerance,
+        }
+    }
+
+    /// Distance from `calories` to the band, `0.0` when inside
+    fn distance(self, calories: u64) -> f64 {
+        let calories = calories as f64;
+        (self.min - calories).max(calories - self.max).max(0.0)
+    }
+}
+
+impl Transfer {
+    /// Moves the snack from an elf to the other, fails if the giver doesn't carry it
+    fn apply(self, inventory: &mut Inventory) -> Result<(), String> {
+        let elf_count = inventory.elves.len();
+        if self.to >= elf_count {
+            return Err(format!("Elf #{} does not exist", self.to));
+        }
+        let giver = inventory
+            .elves
+            .get_mut(self.from)
+            .ok_or_else(|| format!("Elf #{} does not exist", self.from))?;
+        let index = giver
+            .items
+            .iter()
+            .position(|&c| c == self.calories)
+            .ok_or_else(|| {
+                format!(
+                    "Elf #{} has no {} calories snack to give",
+                    self.from, self.calories
+                )
+            })?;
+        giver.items.swap_remove(index);
+        inventory.elves[self.to].items.push(self.calories);
+        Ok(())
+    }
+}
+
+/// Plans whole snack transfers until every elf carries the average calories, give or take
+/// `tolerance`.
+///
+/// Up to [`EXACT_LIMIT`] snacks, the plan has the fewest possible transfers. Larger inventories
+/// get a greedy plan, which is short but not guaranteed to be minimal.
+pub fn plan(inventory: &Inventory, tolerance: u32) -> Result<Vec<Transfer>, String> {
+    let band = Band::new(inventory, tolerance);
+    let snacks: usize = inventory.elves.iter().map(|elf| elf.items.len()).sum();
+    if snacks <= EXACT_LIMIT {
+        exact(inventory, band, tolerance)
+    } else {
+        greedy(inventory, band, tolerance)
+    }
+}
+
+/// Branch and bound search over the final owner of every snack
+struct Search {
+    /// `(owner, calories)`, biggest snacks first as they overflow the band sooner
+    snacks: Vec<(usize, u32)>,
+    /// Index of the last snack of every elf, or `0` when it has none
+    last_owned: Vec<usize>,
+    band: Band,
+    totals: Vec<u64>,
+    /// Calories of the snacks every elf owns and that have no final owner yet
+    unassigned: Vec<u64>,
+    owners: Vec<usize>,
+    /// Fewest moved snacks found yet, with the owners of that plan
+    best: Option<(usize, Vec<usize>)>,
+}
+
+impl Search {
+    /// Lower bound of the snacks still to move: every elf ending under the band with its own
+    /// snacks receives one, every elf overflowing it with them gives one
+    fn moves_needed(&self) -> usize {
+        let totals = self.totals.iter().zip(&self.unassigned);
+        let receivers = totals
+            .clone()
+            .filter(|&(&t, &own)| ((t + own) as f64) < self.band.min)
+            .count();
+        let givers = totals
+            .filter(|&(&t, &own)| (t + own) as f64 > self.band.max)
+            .count();
+        receivers.max(givers)
+    }
+
+    fn run(&mut self, remaining: u64, moved: usize) {
+        let lower_bound = moved + self.moves_needed();
+        if self
+            .best
+            .as_ref()
+            .is_some_and(|(fewest, _)| lower_bound >= *fewest)
+        {
+            return;
+        }
+        let band = self.band;
+        // The remaining snacks must fill the elves under the band, and fit in the room left
+        let missing: f64 = self
+            .totals
+            .iter()
+            .map(|&t| (band.min - t as f64).max(0.0))
+            .sum();
+        let room: f64 = self.totals.iter().map(|&t| band.max - t as f64).sum();
+        if missing > remaining as f64 || room < remaining as f64 {
+            return;
+        }
+        let index = self.owners.len();
+        let Some(&(owner, calories)) = self.snacks.get(index) else {
+            self.best = Some((moved, self.owners.clone()));
+            return;
+        };
+        self.unassigned[owner] -= u64::from(calories);
+        // Keeping the snack is tried first, so cheap plans are found early
+        let elves = (0..self.totals.len()).filter(|&elf| elf != owner);
+        for elf in std::iter::once(owner).chain(elves) {
+            let total = self.totals[elf] + u64::from(calories);
+            if total as f64 > band.max {
+                continue;
+            }
+            // Elves owning none of the remaining snacks are interchangeable when their totals match
+            let interchangeable = |other: usize| {
+                other != owner
+                    && self.last_owned[other] < index
+                    && self.totals[other] == self.totals[elf]
+            };
+            if elf != owner && interchangeable(elf) && (0..elf).any(interchangeable) {
+                continue;
+            }
+            self.totals[elf] = total;
+            self.owners.push(elf);
+            self.run(
+                remaining - u64::from(calories),
+                moved + usize::from(elf != owner),
+            );
+            self.owners.pop();
+            self.totals[elf] -= u64::from(calories);
+        }
+        self.unassigned[owner] += u64::from(calories);
+    }
+}
+
+/// Whether the snacks, biggest first, can be shared so every elf ends in the band.
+///
+/// Owners don't matter here, so elves with the same total are interchangeable and only the first
+/// one is tried, which quickly proves most plans impossible.
+fn feasible(snacks: &[u32], remaining: u64, band: Band, totals: &mut [u64]) -> bool {
+    let missing: f64 = totals.iter().map(|&t| (band.min - t as f64).max(0.0)).sum();
+    let room: f64 = totals.iter().map(|&t| band.max - t as f64).sum();
+    if missing > remaining as f64 || room < remaining as f64 {
+        return false;
+    }
+    let Some((&calories, rest)) = snacks.split_first() else {
+        return true;
+    };
+    for elf in 0..totals.len() {
+        let total = totals[elf] + u64::from(calories);
+        if total as f64 > band.max || totals[..elf].contains(&totals[elf]) {
+            continue;
+        }
+        totals[elf] = total;
+        let found = feasible(rest, remaining - u64::from(calories), band, totals);
+        totals[elf] -= u64::from(calories);
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+/// Finds the fewest transfers by searching the final owner of every snack.
+///
+/// A snack never needs to move twice, so the transfers are the snacks changing owner.
+fn exact(inventory: &Inventory, band: Band, tolerance: u32) -> Result<Vec<Transfer>, String> {
+    let mut snacks: Vec<_> = inventory
+        .elves
+        .iter()
+        .enumerate()
+        .flat_map(|(elf, e)| e.items.iter().map(move |&calories| (elf, calories)))
+        .collect();
+    snacks.sort_unstable_by_key(|&(_, calories)| Reverse(calories));
+    let mut last_owned = vec![0; inventory.elves.len()];
+    for (i, &(elf, _)) in snacks.iter().enumerate() {
+        last_owned[elf] = i;
+    }
+    let remaining = snacks
+        .iter()
+        .map(|&(_, calories)| u64::from(calories))
+        .sum();
+    let calories: Vec<_> = snacks.iter().map(|&(_, calories)| calories).collect();
+    if !feasible(
+        &calories,
+        remaining,
+        band,
+        &mut vec![0; inventory.elves.len()],
+    ) {
+        return Err(format!(
+            "No snack redistribution brings every elf within {tolerance} calories of the average"
+        ));
+    }
+    let mut search = Search {
+        owners: Vec::with_capacity(snacks.len()),
+        snacks,
+        last_owned,
+        band,
+        totals: vec![0; inventory.elves.len()],
+        unassigned: inventory.totals(),
+        best: None,
+    };
+    search.run(remaining, 0);
+    let (_, owners) = search.best.expect("A feasible plan has a best plan");
+    Ok(search
+        .snacks
+        .into_iter()
+        .zip(owners)
+        .filter(|&((from, _), to)| from != to)
+        .map(|((from, calories), to)| Transfer { from, to, calories })
+        .collect())
+}
+
+/// Greedily picks the transfer bringing the elves the closest to the tolerated range, until they
+/// all are in it
+fn greedy(inventory: &Inventory, band: Band, tolerance: u32) -> Result<Vec<Transfer>, String> {
+    let mut inventory = inventory.clone();
+    let mut transfers = Vec::new();
+    loop {
+        let totals = inventory.totals();
+        if totals.iter().all(|&c| band.distance(c) == 0.0) {
+            return Ok(transfers);
+        }
+        let current = &inventory;
+        let elves = || totals.iter().copied().enumerate();
+        let best = elves()
+            .filter(|&(_, total)| total as f64 > band.average)
+            .flat_map(|(from, giver_total)| {
+                elves()
+                    .filter(|&(_, total)| (total as f64) < band.average)
+                    .flat_map(move |(to, taker_total)| {
+                        let before = band.distance(giver_total) + band.distance(taker_total);
+                        current.elves[from].items.iter().map(move |&calories| {
+                            let after = band.distance(giver_total - u64::from(calories))
+                                + band.distance(taker_total + u64::from(calories));
+                            (before - after, Transfer { from, to, calories })
+                        })
+                    })
+            })
+            .filter(|(gain, _)| *gain > 0.0)
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+        let Some((_, transfer)) = best else {
+            let stuck: Vec<_> = elves()
+                .filter(|&(_, total)| band.distance(total) > 0.0)
+                .map(|(elf, total)| format!("#{elf} ({total} calories)"))
+                .collect();
+            return Err(format!(
+                "No snack transfer brings elves {} within {tolerance} calories of the average",
+                stuck.join(", ")
+            ));
+        };
+        transfer.apply(&mut inventory)?;
+        transfers.push(transfer);
+    }
+}
+
+/// Replays `transfers` on `inventory` and checks every elf ends within `tolerance` calories of
+/// the average, returning the resulting inventory
+pub fn validate(
+    inventory: &Inventory,
+    transfers: &[Transfer],
+    tolerance: u32,
+) -> Result<Inventory, String> {
+    let band = Band::new(inventory, tolerance);
+    let mut inventory = inventory.clone();
+    for (i, transfer) in transfers.iter().enumerate() {
+        transfer
+            .apply(&mut inventory)
+            .map_err(|e| format!("Transfer #{}: {e}", i + 1))?;
+    }
+    if let Some((elf, total)) = inventory
+        .totals()
+        .into_iter()
+        .enumerate()
+        .find(|&(_, total)| band.distance(total) > 0.0)
+    {
+        return Err(format!(
+            "Elf #{elf} ends with {total} calories, outside of {:.0}..={:.0}",
+            band.min, band.max
+        ));
+    }
+    Ok(inventory)
+}
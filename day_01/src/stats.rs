@@ -0,0 +1,157 @@
+use std::fmt::Write;
+
+/// Summary statistics over a non-empty set of values
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    sorted: Vec<u64>,
+    mean: f64,
+    std_dev: f64,
+}
+
+/// A histogram bucket covering `min..=max`
+#[derive(Debug, Clone, Copy)]
+pub struct Bucket {
+    pub min: u64,
+    pub max: u64,
+    pub count: usize,
+}
+
+/// Output format of a [`Report`]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Format {
+    #[default]
+    Table,
+    Csv,
+}
+
+/// Named statistics rendered with the same percentiles and histogram resolution
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub series: Vec<(String, Statistics)>,
+    pub percentiles: Vec<f64>,
+    pub buckets: usize,
+}
+
+impl Statistics {
+    /// Computes the statistics of `values`, returns `None` if there are none
+    pub fn new(values: impl IntoIterator<Item = u64>) -> Option<Self> {
+        let mut sorted: Vec<_> = values.into_iter().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+        let len = sorted.len() as f64;
+        let mean = sorted.iter().copied().map(|v| v as f64).sum::<f64>() / len;
+        let variance = sorted
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / len;
+        Some(Self {
+            sorted,
+            mean,
+            std_dev: variance.sqrt(),
+        })
+    }
+
+    pub const fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn min(&self) -> u64 {
+        self.sorted[0]
+    }
+
+    pub fn max(&self) -> u64 {
+        self.sorted[self.sorted.len() - 1]
+    }
+
+    pub const fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Population standard deviation
+    pub const fn std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// Returns the `p`th percentile (`0.0..=100.0`), linearly interpolated between ranks
+    pub fn percentile(&self, p: f64) -> f64 {
+        let position = p.clamp(0.0, 100.0) / 100.0 * (self.sorted.len() - 1) as f64;
+        let (low, high) = (position.floor(), position.ceil());
+        let [a, b] = [low, high].map(|i| self.sorted[i as usize] as f64);
+        (b - a).mul_add(position - low, a)
+    }
+
+    /// Splits `min..=max` into at most `buckets` ranges of equal width
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        let (min, max) = (self.min(), self.max());
+        let width = (max - min + 1).div_ceil(buckets.max(1) as u64);
+        let mut res: Vec<_> = (min..=max)
+            .step_by(width as usize)
+            .map(|start| Bucket {
+                min: start,
+                max: (start + width - 1).min(max),
+                count: 0,
+            })
+            .collect();
+        for &value in &self.sorted {
+            res[((value - min) / width) as usize].count += 1;
+        }
+        res
+    }
+}
+
+impl Report {
+    pub fn render(&self, format: Format) -> String {
+        let mut res = String::new();
+        for (name, stats) in &self.series {
+            let rows = self.rows(stats);
+            match format {
+                Format::Table => {
+                    writeln!(res, "== {name} ==").unwrap();
+                    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+                    for (label, value) in rows {
+                        writeln!(res, "{label:>width$} | {value}").unwrap();
+                    }
+                }
+                Format::Csv => {
+                    if res.is_empty() {
+                        res.push_str("series,metric,value\n");
+                    }
+                    for (label, value) in rows {
+                        writeln!(res, "{name},{label},{value}").unwrap();
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    fn rows(&self, stats: &Statistics) -> Vec<(String, String)> {
+        let mut rows = vec![
+            ("count".to_string(), stats.count().to_string()),
+            ("min".to_string(), stats.min().to_string()),
+            ("max".to_string(), stats.max().to_string()),
+            ("mean".to_string(), format!("{:.2}", stats.mean())),
+            ("median".to_string(), format!("{:.2}", stats.median())),
+            ("std dev".to_string(), format!("{:.2}", stats.std_dev())),
+        ];
+        rows.extend(
+            self.percentiles
+                .iter()
+                .map(|&p| (format!("p{p}"), format!("{:.2}", stats.percentile(p)))),
+        );
+        rows.extend(
+            stats
+                .histogram(self.buckets)
+                .into_iter()
+                .map(|b| (format!("{}-{}", b.min, b.max), b.count.to_string())),
+        );
+        rows
+    }
+}
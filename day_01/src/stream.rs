@@ -0,0 +1,45 @@
+use std::io::BufRead;
+
+use crate::inventory::{parse_line, Elf};
+
+/// Reads elves from any [`BufRead`], yielding each elf as soon as its group is complete.
+///
+/// Only the current group is kept in memory, so arbitrarily large inputs can be processed.
+pub struct ElfReader<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> ElfReader<R> {
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ElfReader<R> {
+    type Item = Result<Elf, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut elf = Elf::default();
+        loop {
+            self.line.clear();
+            self.line_number += 1;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return (!elf.items.is_empty()).then_some(Ok(elf)),
+                Ok(_) => (),
+                Err(e) => return Some(Err(format!("line {}: {e}", self.line_number))),
+            }
+            match parse_line(self.line_number, &self.line) {
+                Ok(Some(calories)) => elf.items.push(calories),
+                Ok(None) if !elf.items.is_empty() => return Some(Ok(elf)),
+                Ok(None) => (),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::game::Game;
+
+const EPSILON: f64 = 1e-9;
+
+/// Optimal mixed strategies when the opponent plays to minimize the player score
+#[derive(Debug, Clone)]
+pub struct Equilibrium {
+    /// Probability of playing every shape, in game order
+    pub strategy: Vec<f64>,
+    /// Opponent probability of playing every shape, in game order
+    pub opponent_strategy: Vec<f64>,
+    /// Expected score per round under optimal play
+    pub value: f64,
+}
+
+/// Computes the Nash equilibrium of the zero-sum game defined by the round scores.
+///
+/// Every score is positive, so the column player problem `max Σy, A·y <= 1, y >= 0` is bounded
+/// and solved with the simplex method. The player strategy is read from the dual values.
+pub fn equilibrium(game: &Game) -> Equilibrium {
+    let shapes: Vec<_> = game.shapes().collect();
+    let n = shapes.len();
+    let rhs = 2 * n;
+    // Columns are the `n` opponent variables, the `n` slack variables and the right hand side
+    let mut tableau: Vec<Vec<f64>> = shapes
+        .iter()
+        .enumerate()
+        .map(|(i, &shape)| {
+            let mut row: Vec<_> = shapes
+                .iter()
+                .map(|&other| f64::from(shape.score_against(other)))
+                .collect();
+            row.resize(rhs + 1, 0.0);
+            row[n + i] = 1.0;
+            row[rhs] = 1.0;
+            row
+        })
+        .collect();
+    let mut objective = vec![0.0; rhs + 1];
+    objective[..n].fill(-1.0);
+    let mut basis: Vec<_> = (n..rhs).collect();
+
+    // Bland's rule: the first improving column enters, which prevents cycling
+    while let Some(column) = (0..rhs).find(|&c| objective[c] < -EPSILON) {
+        let row = (0..n)
+            .filter(|&r| tableau[r][column] > EPSILON)
+            .min_by(|&a, &b| {
+                let ratio = |r: usize| tableau[r][rhs] / tableau[r][column];
+                ratio(a).total_cmp(&ratio(b)).then(basis[a].cmp(&basis[b]))
+            })
+            .expect("Positive scores keep the problem bounded");
+        let pivot = tableau[row][column];
+        tableau[row].iter_mut().for_each(|v| *v /= pivot);
+        let pivot_row = tableau[row].clone();
+        for (_, line) in tableau.iter_mut().enumerate().filter(|(r, _)| *r != row) {
+            let factor = line[column];
+            line.iter_mut()
+                .zip(&pivot_row)
+                .for_each(|(v, p)| *v -= factor * p);
+        }
+        let factor = objective[column];
+        objective
+            .iter_mut()
+            .zip(&pivot_row)
+            .for_each(|(v, p)| *v -= factor * p);
+        basis[row] = column;
+    }
+
+    let value = 1.0 / objective[rhs];
+    let mut opponent_strategy = vec![0.0; n];
+    for (row, &variable) in basis.iter().enumerate() {
+        if variable < n {
+            opponent_strategy[variable] = tableau[row][rhs] * value;
+        }
+    }
+    let strategy = objective[n..rhs].iter().map(|y| y * value).collect();
+    Equilibrium {
+        strategy,
+        opponent_strategy,
+        value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn rock_paper_scissors() {
+        let equilibrium = equilibrium(&Game::rock_paper_scissors());
+        assert_close(&equilibrium.strategy, &[1.0 / 3.0; 3]);
+        assert_close(
+            &equilibrium.opponent_strategy,
+            &[4.0 / 9.0, 1.0 / 9.0, 4.0 / 9.0],
+        );
+        assert!((equilibrium.value - 5.0).abs() < 1e-6);
+    }
+}
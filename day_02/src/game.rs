@@ -0,0 +1,187 @@
+use std::str::FromStr;
+
+/// A shape of a [`Game`], identified by its position in the game definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape {
+    index: usize,
+    shape_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Outcome {
+    Loose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+/// Cyclic game definition with an odd number of shapes.
+///
+/// Every shape beats the `(n - 1) / 2` shapes preceding it in the definition, wrapping around,
+/// and is worth its 1-based position.
+/// Opponent shapes are encoded from `A` and player shapes with the last letters of the alphabet,
+/// so the 3 shapes game uses `A B C` and `X Y Z`.
+#[derive(Debug, Clone)]
+pub struct Game {
+    names: Vec<String>,
+}
+
+impl Shape {
+    /// Position of the shape in its game definition
+    pub const fn index(self) -> usize {
+        self.index
+    }
+
+    pub const fn score(self) -> u32 {
+        self.index as u32 + 1
+    }
+
+    /// Returns the shape to play against `self` for the wanted `outcome`.
+    /// If multiple shapes fit, the highest scoring one is chosen
+    pub fn shape_to_outcome(self, outcome: Outcome) -> Self {
+        let n = self.shape_count;
+        let offsets = match outcome {
+            Outcome::Draw => 0..=0,
+            Outcome::Win => 1..=(n - 1) / 2,
+            Outcome::Loose => n.div_ceil(2)..=n - 1,
+        };
+        offsets
+            .map(|offset| Self {
+                index: (self.index + offset) % n,
+                shape_count: n,
+            })
+            .max_by_key(|shape| shape.index)
+            .unwrap()
+    }
+
+    pub const fn outcome_against(self, other: Self) -> Outcome {
+        let n = self.shape_count;
+        match (self.index + n - other.index) % n {
+            0 => Outcome::Draw,
+            offset if offset <= (n - 1) / 2 => Outcome::Win,
+            _ => Outcome::Loose,
+        }
+    }
+
+    pub const fn score_against(self, other: Self) -> u32 {
+        let outcome = self.outcome_against(other);
+        outcome as u32 + self.score()
+    }
+}
+
+impl Game {
+    pub fn new(names: &[&str]) -> Result<Self, String> {
+        if names.len() < 3 || names.len().is_multiple_of(2) || names.len() > 25 {
+            return Err(format!(
+                "A game needs an odd number of shapes between 3 and 25, got {}",
+                names.len()
+            ));
+        }
+        Ok(Self {
+            names: names.iter().map(ToString::to_string).collect(),
+        })
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(&["Rock", "Paper", "Scissor"]).unwrap()
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(&["Rock", "Spock", "Paper", "Lizard", "Scissor"]).unwrap()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> + '_ {
+        (0..self.names.len()).map(|index| Shape {
+            index,
+            shape_count: self.names.len(),
+        })
+    }
+
+    /// Returns the shape maximizing the expected score against an opponent playing every shape
+    /// with the given probabilities, along with that expected score
+    pub fn best_response(&self, distribution: &[f64]) -> (Shape, f64) {
+        self.shapes()
+            .map(|shape| {
+                let expected: f64 = self
+                    .shapes()
+                    .zip(distribution)
+                    .map(|(other, p)| f64::from(shape.score_against(other)) * p)
+                    .sum();
+                (shape, expected)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap()
+    }
+
+    /// Parses comma separated probabilities of playing every shape, in game order
+    pub fn distribution(&self, s: &str) -> Result<Vec<f64>, String> {
+        let distribution: Vec<f64> = s
+            .split(',')
+            .map(|v| {
+                v.trim()
+                    .parse()
+                    .map_err(|_| format!("{v} is not a valid probability"))
+            })
+            .collect::<Result<_, _>>()?;
+        if distribution.len() != self.names.len() {
+            return Err(format!(
+                "Expected {} probabilities, one per shape, got {}",
+                self.names.len(),
+                distribution.len()
+            ));
+        }
+        if let Some(p) = distribution.iter().find(|p| !(0.0..=1.0).contains(*p)) {
+            return Err(format!("{p} is not a valid probability"));
+        }
+        let sum: f64 = distribution.iter().sum();
+        if (sum - 1.0).abs() > 1e-6 {
+            return Err(format!("Probabilities must sum to 1, got {sum}"));
+        }
+        Ok(distribution)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.index]
+    }
+
+    pub fn opponent_shape(&self, s: &str) -> Result<Shape, String> {
+        self.shapes()
+            .find(|shape| s.as_bytes() == [b'A' + shape.index as u8])
+            .ok_or_else(|| format!("{s} is not a valid opponent shape"))
+    }
+
+    pub const fn opponent_code(&self, shape: Shape) -> char {
+        (b'A' + shape.index as u8) as char
+    }
+
+    pub fn player_code(&self, shape: Shape) -> char {
+        char::from(b'Z' + 1 - self.names.len() as u8 + shape.index as u8)
+    }
+}
+
+impl FromStr for Game {
+    type Err = String;
+
+    /// Parses a preset name (`rps`, `rpsls`) or a comma separated list of shape names
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rps" => Ok(Self::rock_paper_scissors()),
+            "rpsls" => Ok(Self::rock_paper_scissors_lizard_spock()),
+            _ => Self::new(&s.split(',').collect::<Vec<_>>()),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let outcome = match s {
+            "X" => Self::Loose,
+            "Y" => Self::Draw,
+            "Z" => Self::Win,
+            _ => return Err(format!("{s} is not a valis RCS outcome")),
+        };
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,162 @@
+use crate::game::{Game, Shape};
+
+/// Cost of the totals no choice of shapes reaches
+const UNREACHABLE: u16 = u16::MAX;
+
+/// Fewest repeated shapes needed by the rounds from some index onwards, for every previous shape
+/// and total score of those rounds
+#[derive(Debug, Clone)]
+struct Layer {
+    /// Row per previous shape, the last row standing for the first round without previous shape
+    costs: Vec<u16>,
+    /// Lowest total of the rounds, the first column of every row
+    min: u32,
+    width: usize,
+}
+
+impl Layer {
+    /// Layer after the last round: only the null total is reached, without any repeat
+    fn last(shape_count: usize) -> Self {
+        Self {
+            costs: vec![0; shape_count + 1],
+            min: 0,
+            width: 1,
+        }
+    }
+
+    fn totals(&self) -> impl Iterator<Item = u32> {
+        self.min..self.min + self.width as u32
+    }
+
+    fn cost(&self, previous: usize, total: u32) -> u16 {
+        match total.checked_sub(self.min) {
+            Some(column) if (column as usize) < self.width => {
+                self.costs[previous * self.width + column as usize]
+            }
+            _ => UNREACHABLE,
+        }
+    }
+
+    /// Layer of the round before, `scores` being the score of every shape in that round
+    fn before(&self, scores: &[u32]) -> Self {
+        let shape_count = scores.len();
+        let lowest = *scores.iter().min().unwrap();
+        let highest = *scores.iter().max().unwrap();
+        let width = self.width + (highest - lowest) as usize;
+        // The two cheapest shapes of every total, so every previous shape finds the best other one
+        let mut first = vec![(UNREACHABLE, shape_count); width];
+        let mut second = vec![UNREACHABLE; width];
+        for (shape, &score) in scores.iter().enumerate() {
+            let row = &self.costs[shape * self.width..(shape + 1) * self.width];
+            let shift = (score - lowest) as usize;
+            let columns = first[shift..].iter_mut().zip(&mut second[shift..]);
+            for ((first, second), &cost) in columns.zip(row) {
+                if cost < first.0 {
+                    *second = first.0;
+                    *first = (cost, shape);
+                } else if cost < *second {
+                    *second = cost;
+                }
+            }
+        }
+        let mut costs = vec![UNREACHABLE; (shape_count + 1) * width];
+        for (previous, row) in costs.chunks_mut(width).enumerate() {
+            for ((cost, &(best, shape)), &other) in row.iter_mut().zip(&first).zip(&second) {
+                *cost = if previous != shape || best == UNREACHABLE {
+                    best
+                } else {
+                    other.min(best.saturating_add(1).min(UNREACHABLE - 1))
+                };
+            }
+        }
+        Self {
+            costs,
+            min: self.min + lowest,
+            width,
+        }
+    }
+}
+
+/// Chooses a shape for every `opponent` move so the rounds score exactly `target`, playing the
+/// same shape twice in a row as rarely as possible.
+///
+/// The fewest repeats of every round suffix are computed backwards for each previous shape and
+/// total, then shapes are picked forwards along an optimal path. Only about `√n` layers are kept
+/// at once, the others being recomputed when needed.
+pub fn generate(game: &Game, opponent: &[Shape], target: u32) -> Result<Vec<Shape>, String> {
+    let shapes: Vec<_> = game.shapes().collect();
+    let scores: Vec<Vec<u32>> = opponent
+        .iter()
+        .map(|&other| shapes.iter().map(|s| s.score_against(other)).collect())
+        .collect();
+    let step = opponent.len().isqrt().max(1);
+    // `checkpoints[k]` is the layer of the rounds `k * step..`
+    let mut layer = Layer::last(shapes.len());
+    let mut checkpoints = vec![layer.clone()];
+    for (i, scores) in scores.iter().enumerate().rev() {
+        layer = layer.before(scores);
+        if i.is_multiple_of(step) {
+            checkpoints.push(layer.clone());
+        }
+    }
+    checkpoints.reverse();
+    let first = &checkpoints[0];
+    let no_previous = shapes.len();
+    if first.cost(no_previous, target) == UNREACHABLE {
+        let reachable: Vec<_> = first
+            .totals()
+            .filter(|&total| first.cost(no_previous, total) != UNREACHABLE)
+            .collect();
+        return Err(format!(
+            "No guide scores exactly {target}, reachable scores lie between {} and {}",
+            reachable.first().unwrap_or(&0),
+            reachable.last().unwrap_or(&0)
+        ));
+    }
+
+    let mut remaining = target;
+    let mut previous = no_previous;
+    let mut moves = Vec::with_capacity(opponent.len());
+    for (k, start) in (0..opponent.len()).step_by(step).enumerate() {
+        let end = (start + step).min(opponent.len());
+        // `layers[j]` is the layer of the rounds `start + j + 1..`
+        let mut layers = vec![checkpoints[k + 1].clone()];
+        for i in (start + 1..end).rev() {
+            layers.push(layers.last().unwrap().before(&scores[i]));
+        }
+        layers.reverse();
+        for (i, next) in (start..end).zip(&layers) {
+            let shape = (0..shapes.len())
+                .filter(|&shape| scores[i][shape] <= remaining)
+                .min_by_key(|&shape| {
+                    let cost = next.cost(shape, remaining - scores[i][shape]);
+                    cost.saturating_add(u16::from(shape == previous))
+                })
+                .unwrap();
+            remaining -= scores[i][shape];
+            previous = shape;
+            moves.push(shapes[shape]);
+        }
+    }
+    Ok(moves)
+}
+
+/// Counts the rounds playing the same shape as the round before
+pub fn repeats(moves: &[Shape]) -> usize {
+    moves.windows(2).filter(|w| w[0] == w[1]).count()
+}
+
+/// Formats rounds as strategy guide lines, `A Y` for instance
+pub fn format_guide(game: &Game, opponent: &[Shape], moves: &[Shape]) -> String {
+    opponent
+        .iter()
+        .zip(moves)
+        .map(|(&other, &shape)| {
+            format!(
+                "{} {}\n",
+                game.opponent_code(other),
+                game.player_code(shape)
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,117 @@
+use std::str::FromStr;
+
+use crate::game::{Game, Outcome, Shape};
+
+/// A strategy guide line, the second column being kept encrypted
+#[derive(Debug, Clone, Copy)]
+pub struct Round {
+    pub opponent: Shape,
+    pub code: char,
+}
+
+#[derive(Debug, Clone)]
+pub struct Guide {
+    pub rounds: Vec<Round>,
+}
+
+/// How the second column of a [`Guide`] is decrypted
+#[derive(Debug, Clone)]
+pub enum Mapping {
+    /// Each code is the shape to play
+    Shapes(Vec<(char, Shape)>),
+    /// Each code is the outcome to reach
+    Outcomes(Vec<(char, Outcome)>),
+}
+
+impl Guide {
+    pub fn parse(game: &Game, s: &str) -> Result<Self, String> {
+        let rounds = s
+            .lines()
+            .map(|line| {
+                let (a, b) = line
+                    .split_once(' ')
+                    .ok_or_else(|| format!("{line} is not a valid round"))?;
+                let opponent = game.opponent_shape(a)?;
+                let code = char::from_str(b).map_err(|_| format!("{b} is not a valid code"))?;
+                Ok(Round { opponent, code })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { rounds })
+    }
+
+    /// Returns every distinct second column code, sorted
+    pub fn codes(&self) -> Vec<char> {
+        let mut codes: Vec<_> = self.rounds.iter().map(|round| round.code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        codes
+    }
+
+    /// Returns the decrypted shape played every round
+    pub fn moves(&self, mapping: &Mapping) -> Result<Vec<Shape>, String> {
+        self.rounds
+            .iter()
+            .map(|round| mapping.shape(round))
+            .collect()
+    }
+
+    pub fn score(&self, mapping: &Mapping) -> Result<u32, String> {
+        self.rounds
+            .iter()
+            .map(|round| {
+                let shape = mapping.shape(round)?;
+                Ok(shape.score_against(round.opponent))
+            })
+            .sum()
+    }
+}
+
+impl Mapping {
+    /// Codes are the game player shape codes, `X Y Z` for the 3 shapes game
+    pub fn default_shapes(game: &Game) -> Self {
+        Self::Shapes(
+            game.shapes()
+                .map(|shape| (game.player_code(shape), shape))
+                .collect(),
+        )
+    }
+
+    /// `X` to loose, `Y` to draw and `Z` to win
+    pub fn default_outcomes() -> Self {
+        Self::Outcomes(
+            ['X', 'Y', 'Z']
+                .map(|c| (c, Outcome::from_str(&c.to_string()).unwrap()))
+                .to_vec(),
+        )
+    }
+
+    /// Returns the shape played for `round`
+    pub fn shape(&self, round: &Round) -> Result<Shape, String> {
+        let missing = || format!("{} is not mapped", round.code);
+        match self {
+            Self::Shapes(shapes) => shapes
+                .iter()
+                .find_map(|(c, shape)| (*c == round.code).then_some(*shape))
+                .ok_or_else(missing),
+            Self::Outcomes(outcomes) => outcomes
+                .iter()
+                .find_map(|(c, outcome)| (*c == round.code).then_some(*outcome))
+                .map(|outcome| round.opponent.shape_to_outcome(outcome))
+                .ok_or_else(missing),
+        }
+    }
+
+    pub fn describe(&self, game: &Game) -> String {
+        let entries: Vec<_> = match self {
+            Self::Shapes(shapes) => shapes
+                .iter()
+                .map(|(c, shape)| format!("{c}={}", game.name(*shape)))
+                .collect(),
+            Self::Outcomes(outcomes) => outcomes
+                .iter()
+                .map(|(c, outcome)| format!("{c}={outcome:?}"))
+                .collect(),
+        };
+        entries.join(" ")
+    }
+}
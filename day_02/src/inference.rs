@@ -0,0 +1,72 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{
+    game::{Game, Outcome},
+    guide::{Guide, Mapping, Round},
+};
+
+/// Returns every ordered selection of `k` distinct values in `0..n`
+fn arrangements(k: usize, n: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    arrangements(k - 1, n)
+        .into_iter()
+        .flat_map(|prefix| {
+            (0..n)
+                .filter(|i| !prefix.contains(i))
+                .map(|i| [prefix.as_slice(), &[i]].concat())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Scores `guide` under every way of decrypting its second column, either as shapes or as
+/// outcomes, best score first
+pub fn evaluate_mappings(game: &Game, guide: &Guide) -> Vec<(Mapping, u32)> {
+    let codes = guide.codes();
+    let shapes: Vec<_> = game.shapes().collect();
+    let outcomes = [Outcome::Loose, Outcome::Draw, Outcome::Win];
+    // Identical rounds are only scored once per mapping
+    let mut tally = HashMap::new();
+    for round in &guide.rounds {
+        *tally.entry((round.code, round.opponent)).or_insert(0) += 1;
+    }
+    let shape_mappings = arrangements(codes.len(), shapes.len())
+        .into_iter()
+        .map(|a| {
+            Mapping::Shapes(
+                codes
+                    .iter()
+                    .copied()
+                    .zip(a.iter().map(|&i| shapes[i]))
+                    .collect(),
+            )
+        });
+    let outcome_mappings = arrangements(codes.len(), outcomes.len())
+        .into_iter()
+        .map(|a| {
+            Mapping::Outcomes(
+                codes
+                    .iter()
+                    .copied()
+                    .zip(a.iter().map(|&i| outcomes[i]))
+                    .collect(),
+            )
+        });
+    let mut res: Vec<_> = shape_mappings
+        .chain(outcome_mappings)
+        .map(|mapping| {
+            let score = tally
+                .iter()
+                .map(|(&(code, opponent), count)| {
+                    let shape = mapping.shape(&Round { opponent, code }).unwrap();
+                    shape.score_against(opponent) * count
+                })
+                .sum();
+            (mapping, score)
+        })
+        .collect();
+    res.sort_by_key(|(_, score)| Reverse(*score));
+    res
+}
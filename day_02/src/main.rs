@@ -1,106 +1,194 @@
-use std::str::FromStr;
+mod equilibrium;
+mod game;
+mod generator;
+mod guide;
+mod inference;
+mod prediction;
+mod tournament;
 
-#[derive(Clone, Copy)]
-#[repr(u32)]
-enum Shape {
-    Rock = 1,
-    Paper = 2,
-    Scissor = 3,
-}
+use std::{
+    env,
+    fs::{read_to_string, write},
+    str::FromStr,
+};
 
-#[derive(Clone, Copy)]
-#[repr(u32)]
-enum Outcome {
-    Loose = 0,
-    Draw = 3,
-    Win = 6,
-}
+use equilibrium::equilibrium;
+use game::Game;
+use generator::{format_guide, generate, repeats};
+use guide::{Guide, Mapping};
+use inference::evaluate_mappings;
+use prediction::{backtest, Model, Predictor};
+use tournament::{Player, Tournament};
 
-impl Shape {
-    pub const fn shape_to_outcome(self, outcome: Outcome) -> Self {
-        match outcome {
-            Outcome::Win => match self {
-                Self::Rock => Self::Paper,
-                Self::Paper => Self::Scissor,
-                Self::Scissor => Self::Rock,
-            },
-            Outcome::Loose => match self {
-                Self::Rock => Self::Scissor,
-                Self::Paper => Self::Rock,
-                Self::Scissor => Self::Paper,
-            },
-            Outcome::Draw => self,
-        }
+/// Scores the guide under every possible decryption
+fn mapping(game: &Game, guide: &Guide) {
+    let evaluations = evaluate_mappings(game, guide);
+    for (mapping, score) in &evaluations {
+        println!("{}: {score}", mapping.describe(game));
     }
-
-    pub const fn outcome_against(self, other: Self) -> Outcome {
-        match (self, other) {
-            (Self::Rock, Self::Scissor)
-            | (Self::Scissor, Self::Paper)
-            | (Self::Paper, Self::Rock) => Outcome::Win,
-            (Self::Rock, Self::Paper)
-            | (Self::Scissor, Self::Rock)
-            | (Self::Paper, Self::Scissor) => Outcome::Loose,
-            _ => Outcome::Draw,
-        }
+    if let Some((mapping, score)) = evaluations.first() {
+        println!("Best mapping: {} with {score}", mapping.describe(game));
     }
+}
 
-    pub const fn score_against(self, other: Self) -> u32 {
-        let outcome = self.outcome_against(other);
-        outcome as u32 + self as u32
+/// Compares the guide with strategies predicting the opponent moves, both fitted on the whole
+/// guide and backtested after `k` training rounds
+fn predict(game: &Game, guide: &Guide, k: usize) {
+    let opponent: Vec<_> = guide.rounds.iter().map(|round| round.opponent).collect();
+    let moves = guide.moves(&Mapping::default_shapes(game)).unwrap();
+    for model in [Model::Frequency, Model::Markov] {
+        let predictor = Predictor::train(model, game, &opponent);
+        let fitted = predictor.evaluate(game, None, &opponent, &moves);
+        println!(
+            "{model:?} model: {} vs guide {} over {} rounds",
+            fitted.predicted_score, fitted.guide_score, fitted.rounds
+        );
+        let tested = backtest(model, game, &opponent, &moves, k);
+        println!(
+            "{model:?} backtest: {} vs guide {} over {} rounds after {k} training rounds",
+            tested.predicted_score, tested.guide_score, tested.rounds
+        );
     }
 }
 
-impl FromStr for Shape {
-    type Err = String;
+/// Prints the equilibrium strategy and the best response to `distribution`, defaulting to the
+/// guide opponent move frequencies
+fn optimal_play(game: &Game, guide: &Guide, distribution: Option<Vec<f64>>) {
+    let format = |probabilities: &[f64]| {
+        let entries: Vec<_> = game
+            .shapes()
+            .zip(probabilities)
+            .map(|(shape, p)| format!("{}={p:.3}", game.name(shape)))
+            .collect();
+        entries.join(" ")
+    };
+    let equilibrium = equilibrium(game);
+    println!("Equilibrium strategy: {}", format(&equilibrium.strategy));
+    println!(
+        "Equilibrium opponent strategy: {}",
+        format(&equilibrium.opponent_strategy)
+    );
+    println!("Expected score per round: {:.3}", equilibrium.value);
+    let distribution = distribution.unwrap_or_else(|| {
+        let len = guide.rounds.len() as f64;
+        game.shapes()
+            .map(|shape| {
+                let count = guide.rounds.iter().filter(|r| r.opponent == shape).count();
+                count as f64 / len
+            })
+            .collect()
+    });
+    let (response, expected) = game.best_response(&distribution);
+    println!(
+        "Best response to {}: {} with {expected:.3} per round",
+        format(&distribution),
+        game.name(response)
+    );
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rcs = match s {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissor,
-            _ => return Err(format!("{s} is not a valis RCS action")),
-        };
-        Ok(rcs)
+/// Generates a guide scoring exactly `target` against the guide opponent moves, and checks it
+/// parses back to the same score
+fn generate_guide(game: &Game, guide: &Guide, target: u32) -> Result<String, String> {
+    let opponent: Vec<_> = guide.rounds.iter().map(|round| round.opponent).collect();
+    let moves = generate(game, &opponent, target)?;
+    let res = format_guide(game, &opponent, &moves);
+    let score = Guide::parse(game, &res)?.score(&Mapping::default_shapes(game))?;
+    if score != target {
+        return Err(format!(
+            "Generated guide scores {score} instead of {target} once parsed back"
+        ));
     }
+    eprintln!("Repeated shapes: {}", repeats(&moves));
+    Ok(res)
 }
 
-impl FromStr for Outcome {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let outcome = match s {
-            "X" => Self::Loose,
-            "Y" => Self::Draw,
-            "Z" => Self::Win,
-            _ => return Err(format!("{s} is not a valis RCS outcome")),
-        };
-        Ok(outcome)
+/// Runs a tournament between the guides of `files`, `--elimination` being an optional first
+/// parameter
+fn tournament(game: &Game, files: &[String]) {
+    let elimination = files.first().is_some_and(|f| f == "--elimination");
+    let mapping = Mapping::default_shapes(game);
+    let players: Vec<_> = files[usize::from(elimination)..]
+        .iter()
+        .map(|file| {
+            let guide = Guide::parse(game, &read_to_string(file).unwrap()).unwrap();
+            Player {
+                name: file.clone(),
+                moves: guide.moves(&mapping).unwrap(),
+            }
+        })
+        .collect();
+    let tournament = if elimination {
+        Tournament::elimination(&players)
+    } else {
+        Tournament::round_robin(&players)
+    };
+    for (i, round) in tournament.rounds.iter().enumerate() {
+        println!("Round {}:", i + 1);
+        for played in round {
+            let [a, b] = played.players.map(|p| &players[p].name);
+            println!("  {a} {} - {} {b}", played.scores[0], played.scores[1]);
+        }
+    }
+    println!("Standings:");
+    for player in tournament.ranking() {
+        let s = tournament.standings[player];
+        println!(
+            "  {}: {}W {}D {}L, score = {}",
+            players[player].name, s.wins, s.draws, s.losses, s.score
+        );
     }
 }
 
 fn main() {
-    let file: Vec<(&str, &str)> = include_str!("../input.txt")
-        .lines()
-        .map(|line| line.split_once(' ').unwrap())
-        .collect();
-    let part1_score: u32 = file
-        .iter()
-        .map(|(a, b)| {
-            let enemy_shape = Shape::from_str(a).unwrap();
-            let my_shape = Shape::from_str(b).unwrap();
-            my_shape.score_against(enemy_shape)
-        })
-        .sum();
-    println!("Part 1 score = {part1_score}");
-    let part2_score: u32 = file
-        .iter()
-        .map(|(a, b)| {
-            let enemy_shape = Shape::from_str(a).unwrap();
-            let outcome = Outcome::from_str(b).unwrap();
-            let my_shape = enemy_shape.shape_to_outcome(outcome);
-            my_shape.score_against(enemy_shape)
-        })
-        .sum();
-    println!("Part 2 score = {part2_score}");
+    // `day_02 [--game <rps | rpsls | name,name,...>] [<command> <params>...]` with commands:
+    // - `mapping`
+    // - `tournament [--elimination] <guide>...`
+    // - `predict [<training rounds>]`
+    // - `equilibrium [<opponent probabilities, comma separated>]`
+    // - `generate <target score> [<output file>]`
+    let mut game = Game::rock_paper_scissors();
+    let mut params = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--game" => game = Game::from_str(&args.next().expect("Missing game")).unwrap(),
+            _ => params.push(arg),
+        }
+    }
+    let shapes: Vec<_> = game.shapes().map(|shape| game.name(shape)).collect();
+    println!("Game: {}", shapes.join(", "));
+
+    let guide = Guide::parse(&game, include_str!("../input.txt")).unwrap();
+    match params.split_first() {
+        None => {
+            let part1_score = guide.score(&Mapping::default_shapes(&game)).unwrap();
+            println!("Part 1 score = {part1_score}");
+            let part2_score = guide.score(&Mapping::default_outcomes()).unwrap();
+            println!("Part 2 score = {part2_score}");
+        }
+        Some((command, [])) if command == "mapping" => mapping(&game, &guide),
+        Some((command, files)) if command == "tournament" => tournament(&game, files),
+        Some((command, params)) if command == "predict" && params.len() <= 1 => {
+            let k = params
+                .first()
+                .map_or(guide.rounds.len() / 2, |k| k.parse().unwrap());
+            predict(&game, &guide, k);
+        }
+        Some((command, params)) if command == "equilibrium" && params.len() <= 1 => {
+            match params.first().map(|p| game.distribution(p)).transpose() {
+                Ok(distribution) => optimal_play(&game, &guide, distribution),
+                Err(e) => println!("{e}"),
+            }
+        }
+        Some((command, [target, output @ ..])) if command == "generate" && output.len() <= 1 => {
+            match generate_guide(&game, &guide, target.parse().unwrap()) {
+                Ok(guide) => match output.first() {
+                    Some(path) => write(path, guide).unwrap(),
+                    None => print!("{guide}"),
+                },
+                Err(e) => println!("{e}"),
+            }
+        }
+        Some((command, _)) => panic!("Unknown command `{command}`"),
+    }
 }
@@ -0,0 +1,102 @@
+use crate::game::{Game, Shape};
+
+/// How the opponent next move is predicted
+#[derive(Debug, Clone, Copy)]
+pub enum Model {
+    /// Moves are drawn from the overall move frequencies
+    Frequency,
+    /// Moves depend on the previous opponent move (first order Markov chain)
+    Markov,
+}
+
+/// Opponent model fitted on a sequence of observed moves
+#[derive(Debug, Clone)]
+pub struct Predictor {
+    model: Model,
+    counts: Vec<u32>,
+    transitions: Vec<Vec<u32>>,
+}
+
+/// Scores of the predicted strategy and of the guide over the same rounds
+#[derive(Debug, Clone, Copy)]
+pub struct Evaluation {
+    pub rounds: usize,
+    pub predicted_score: u32,
+    pub guide_score: u32,
+}
+
+impl Predictor {
+    pub fn train(model: Model, game: &Game, moves: &[Shape]) -> Self {
+        let n = game.shapes().count();
+        let mut counts = vec![0; n];
+        let mut transitions = vec![vec![0; n]; n];
+        for (i, shape) in moves.iter().enumerate() {
+            counts[shape.index()] += 1;
+            if let Some(previous) = i.checked_sub(1).map(|p| moves[p]) {
+                transitions[previous.index()][shape.index()] += 1;
+            }
+        }
+        Self {
+            model,
+            counts,
+            transitions,
+        }
+    }
+
+    /// Returns the probability of every opponent shape, with add-one smoothing
+    pub fn distribution(&self, previous: Option<Shape>) -> Vec<f64> {
+        let counts = match (self.model, previous) {
+            (Model::Markov, Some(previous)) => &self.transitions[previous.index()],
+            _ => &self.counts,
+        };
+        let total: u32 = counts.iter().map(|c| c + 1).sum();
+        counts
+            .iter()
+            .map(|c| f64::from(c + 1) / f64::from(total))
+            .collect()
+    }
+
+    /// Plays the best response to the predicted move of every round of `opponent` and compares
+    /// it with the `guide` moves. `previous` is the opponent move preceding the first round
+    pub fn evaluate(
+        &self,
+        game: &Game,
+        previous: Option<Shape>,
+        opponent: &[Shape],
+        guide: &[Shape],
+    ) -> Evaluation {
+        let previous_moves = std::iter::once(previous).chain(opponent.iter().copied().map(Some));
+        let predicted_score = opponent
+            .iter()
+            .zip(previous_moves)
+            .map(|(&shape, previous)| {
+                let (response, _) = game.best_response(&self.distribution(previous));
+                response.score_against(shape)
+            })
+            .sum();
+        let guide_score = guide
+            .iter()
+            .zip(opponent)
+            .map(|(&shape, &other)| shape.score_against(other))
+            .sum();
+        Evaluation {
+            rounds: opponent.len(),
+            predicted_score,
+            guide_score,
+        }
+    }
+}
+
+/// Trains a model on the first `k` rounds and evaluates it on the remaining ones
+pub fn backtest(
+    model: Model,
+    game: &Game,
+    opponent: &[Shape],
+    guide: &[Shape],
+    k: usize,
+) -> Evaluation {
+    let k = k.min(opponent.len());
+    let predictor = Predictor::train(model, game, &opponent[..k]);
+    let previous = k.checked_sub(1).map(|i| opponent[i]);
+    predictor.evaluate(game, previous, &opponent[k..], &guide[k..])
+}
@@ -0,0 +1,127 @@
+use std::cmp::Ordering;
+
+use crate::game::Shape;
+
+/// A tournament participant, playing the shapes of its strategy guide in order
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub name: String,
+    pub moves: Vec<Shape>,
+}
+
+/// Two players facing each other over every round of the shortest guide
+#[derive(Debug, Clone, Copy)]
+pub struct Match {
+    pub players: [usize; 2],
+    pub scores: [u32; 2],
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Standing {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    /// Matches grouped by tournament round
+    pub rounds: Vec<Vec<Match>>,
+    /// Standing of every player, in player order
+    pub standings: Vec<Standing>,
+}
+
+impl Match {
+    fn play(players: &[Player], a: usize, b: usize) -> Self {
+        let scores = players[a].moves.iter().zip(&players[b].moves).fold(
+            [0, 0],
+            |[score_a, score_b], (&move_a, &move_b)| {
+                [
+                    score_a + move_a.score_against(move_b),
+                    score_b + move_b.score_against(move_a),
+                ]
+            },
+        );
+        Self {
+            players: [a, b],
+            scores,
+        }
+    }
+
+    /// Returns the winning player, `None` on a draw
+    pub fn winner(&self) -> Option<usize> {
+        match self.scores[0].cmp(&self.scores[1]) {
+            Ordering::Greater => Some(self.players[0]),
+            Ordering::Less => Some(self.players[1]),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+impl Tournament {
+    fn new(player_count: usize) -> Self {
+        Self {
+            rounds: Vec::new(),
+            standings: vec![Standing::default(); player_count],
+        }
+    }
+
+    fn record(&mut self, round: Vec<Match>) {
+        for game in &round {
+            let winner = game.winner();
+            for (player, score) in game.players.into_iter().zip(game.scores) {
+                let standing = &mut self.standings[player];
+                standing.score += score;
+                match winner {
+                    None => standing.draws += 1,
+                    Some(w) if w == player => standing.wins += 1,
+                    Some(_) => standing.losses += 1,
+                }
+            }
+        }
+        self.rounds.push(round);
+    }
+
+    /// Every player faces every other player once
+    pub fn round_robin(players: &[Player]) -> Self {
+        let mut tournament = Self::new(players.len());
+        let round = (0..players.len())
+            .flat_map(|a| (a + 1..players.len()).map(move |b| (a, b)))
+            .map(|(a, b)| Match::play(players, a, b))
+            .collect();
+        tournament.record(round);
+        tournament
+    }
+
+    /// Single elimination bracket in player order, an odd player out getting a bye.
+    /// A drawn match is won by the player listed first
+    pub fn elimination(players: &[Player]) -> Self {
+        let mut tournament = Self::new(players.len());
+        let mut remaining: Vec<_> = (0..players.len()).collect();
+        while remaining.len() > 1 {
+            let round: Vec<_> = remaining
+                .chunks_exact(2)
+                .map(|pair| Match::play(players, pair[0], pair[1]))
+                .collect();
+            let bye = (remaining.len() % 2 == 1).then(|| remaining[remaining.len() - 1]);
+            remaining = round
+                .iter()
+                .map(|game| game.winner().unwrap_or(game.players[0]))
+                .chain(bye)
+                .collect();
+            tournament.record(round);
+        }
+        tournament
+    }
+
+    /// Returns player indexes sorted by wins, draws and then total score
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut res: Vec<_> = (0..self.standings.len()).collect();
+        res.sort_by_key(|&i| {
+            let s = self.standings[i];
+            std::cmp::Reverse((s.wins, s.draws, s.score))
+        });
+        res
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{item_set::ItemSet, priority::PriorityTable, RuckSack};
+
+/// Consecutive rucksacks of an elf group, and the items they all carry
+#[derive(Debug, Clone, Copy)]
+pub struct Group {
+    /// 0-based index of the first rucksack of the group
+    pub start: usize,
+    pub size: usize,
+    pub candidates: ItemSet,
+}
+
+impl Group {
+    /// Returns the group badge, which must be the only item shared by every rucksack
+    pub fn badge(&self, table: &PriorityTable) -> Result<u8, String> {
+        let lines = format!("rucksacks {}-{}", self.start + 1, self.start + self.size);
+        match self.candidates.len() {
+            0 => Err(format!("Group of {lines} has no common item")),
+            1 => Ok(self.candidates.iter().next().unwrap()),
+            _ => {
+                let items: String = self.candidates.iter().map(|id| table.item(id)).collect();
+                Err(format!(
+                    "Group of {lines} has several badge candidates: {items}"
+                ))
+            }
+        }
+    }
+}
+
+/// Splits `rucksacks` in groups of `size`, a trailing partial group being an error
+pub fn groups(rucksacks: &[RuckSack], size: usize) -> Result<Vec<Group>, String> {
+    if size == 0 {
+        return Err("Group size must be positive".to_string());
+    }
+    if !rucksacks.len().is_multiple_of(size) {
+        return Err(format!(
+            "The last {} rucksacks form a partial group, expected groups of {size}",
+            rucksacks.len() % size
+        ));
+    }
+    let groups = rucksacks
+        .chunks(size)
+        .enumerate()
+        .map(|(i, chunk)| Group {
+            start: i * size,
+            size,
+            candidates: chunk
+                .iter()
+                .map(RuckSack::all_items)
+                .reduce(ItemSet::intersection)
+                .unwrap_or_default(),
+        })
+        .collect();
+    Ok(groups)
+}
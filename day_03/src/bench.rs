@@ -0,0 +1,155 @@
+use std::{collections::HashSet, str::FromStr, time::Instant};
+
+use crate::{item_set::ItemSet, priority::PriorityTable, RuckSack};
+
+/// Previous `HashSet` based rucksack, kept as the benchmark baseline
+struct HashSetRuckSack {
+    items_left: HashSet<u8>,
+    items_right: HashSet<u8>,
+}
+
+impl HashSetRuckSack {
+    fn common_items(&self) -> Vec<u8> {
+        let mut res = Vec::new();
+        for item in &self.items_left {
+            if self.items_right.contains(item) {
+                res.push(*item);
+            }
+        }
+        res
+    }
+
+    fn all_items(&self) -> HashSet<u8> {
+        let mut res = self.items_right.clone();
+        res.extend(self.items_left.iter().copied());
+        res
+    }
+}
+
+impl FromStr for HashSetRuckSack {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<_> = s.chars().collect();
+        let (left, right) = chars.split_at(chars.len() / 2);
+
+        let value = |item: &char| {
+            if item.is_ascii_lowercase() {
+                Ok((*item as u8).saturating_sub(b'a') + 1)
+            } else if item.is_ascii_uppercase() {
+                Ok((*item as u8).saturating_sub(b'A') + 27)
+            } else {
+                Err(format!("{item} is not a valid item"))
+            }
+        };
+        let items_left = left.iter().map(value).collect::<Result<_, _>>()?;
+        let items_right = right.iter().map(value).collect::<Result<_, _>>()?;
+        Ok(Self {
+            items_left,
+            items_right,
+        })
+    }
+}
+
+/// Xorshift pseudo random generator, good enough for input generation
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+/// Generates groups of 3 rucksacks sharing exactly one badge
+fn generate(groups: usize) -> String {
+    let alphabet: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut rng = Rng(0x2022_1203);
+    let mut res = String::new();
+    for _ in 0..groups {
+        let mut items = alphabet.clone();
+        let badge = items.swap_remove(rng.next(items.len()));
+        // Every rucksack draws from its own third of the remaining items
+        for pool in items.chunks(items.len() / 3) {
+            let len = 2 * (5 + rng.next(20));
+            let mut rucksack: Vec<_> = (0..len).map(|_| pool[rng.next(pool.len())]).collect();
+            rucksack[rng.next(len)] = badge;
+            res.extend(rucksack);
+            res.push('\n');
+        }
+    }
+    res
+}
+
+fn with_item_set(input: &str) -> (u32, u32) {
+    let table = PriorityTable::default();
+    let rucksacks: Vec<_> = input
+        .lines()
+        .map(|line| RuckSack::parse(line, &table).unwrap())
+        .collect();
+    let common = rucksacks
+        .iter()
+        .flat_map(|r| r.common_items().iter())
+        .map(|id| table.priority(id))
+        .sum();
+    let badges = rucksacks
+        .chunks(3)
+        .map(|chunk| {
+            let badges = chunk
+                .iter()
+                .map(RuckSack::all_items)
+                .reduce(ItemSet::intersection)
+                .unwrap();
+            table.priority(badges.iter().next().unwrap())
+        })
+        .sum();
+    (common, badges)
+}
+
+fn with_hash_set(input: &str) -> (u32, u32) {
+    let rucksacks: Vec<_> = input
+        .lines()
+        .map(|line| HashSetRuckSack::from_str(line).unwrap())
+        .collect();
+    let common = rucksacks
+        .iter()
+        .flat_map(HashSetRuckSack::common_items)
+        .map(u32::from)
+        .sum();
+    let badges = rucksacks
+        .chunks(3)
+        .map(|chunk| {
+            let [b_items, c_items] = [chunk[1].all_items(), chunk[2].all_items()];
+            u32::from(
+                chunk[0]
+                    .all_items()
+                    .iter()
+                    .copied()
+                    .find(|item| b_items.contains(item) && c_items.contains(item))
+                    .unwrap(),
+            )
+        })
+        .sum();
+    (common, badges)
+}
+
+/// Compares the `ItemSet` and `HashSet` implementations on `count` generated rucksacks
+pub fn run(count: usize) {
+    let input = generate(count.div_ceil(3));
+    let mut results = Vec::new();
+    for (name, solve) in [
+        ("ItemSet", with_item_set as fn(&str) -> (u32, u32)),
+        ("HashSet", with_hash_set),
+    ] {
+        let start = Instant::now();
+        let (common, badges) = solve(&input);
+        println!(
+            "{name}: common items sum {common}, badges sum {badges} in {:?}",
+            start.elapsed()
+        );
+        results.push((common, badges));
+    }
+    assert_eq!(results[0], results[1], "Implementations disagree");
+}
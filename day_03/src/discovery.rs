@@ -0,0 +1,189 @@
+use crate::RuckSack;
+
+/// Outcome of [`discover`]
+#[derive(Debug, Clone)]
+pub enum Discovery {
+    /// Every rucksack belongs to a group of three sharing exactly one item type
+    Partition(Vec<[usize; 3]>),
+    /// Rucksacks left out of the largest grouping found, `minimal` when the search proved no
+    /// grouping leaves fewer rucksacks out
+    Blocked {
+        rucksacks: Vec<usize>,
+        minimal: bool,
+    },
+}
+
+/// Search nodes explored looking for the largest grouping before giving up on proving it
+const PACKING_BUDGET: usize = 1_000_000;
+
+/// Exact cover search state
+struct Search<'a> {
+    /// Candidate groups of every rucksack
+    candidates: Vec<Vec<[usize; 3]>>,
+    assigned: Vec<bool>,
+    /// Rucksacks the packing search decided to leave out of every group
+    left_out: Vec<bool>,
+    groups: Vec<[usize; 3]>,
+    /// Largest grouping found
+    best: Vec<[usize; 3]>,
+    /// Nodes the packing search may still explore
+    budget: usize,
+    rucksacks: &'a [RuckSack],
+}
+
+impl Search<'_> {
+    /// Assigns the rucksack with the fewest available candidate groups first
+    fn run(&mut self) -> bool {
+        let available = |group: &[usize; 3]| group.iter().all(|&r| !self.assigned[r]);
+        let next = (0..self.rucksacks.len())
+            .filter(|&r| !self.assigned[r])
+            .map(|r| {
+                (
+                    r,
+                    self.candidates[r].iter().filter(|g| available(g)).count(),
+                )
+            })
+            .min_by_key(|&(_, count)| count);
+        let Some((rucksack, _)) = next else {
+            return true;
+        };
+        let options: Vec<_> = self.candidates[rucksack]
+            .iter()
+            .copied()
+            .filter(available)
+            .collect();
+        for group in options {
+            for r in group {
+                self.assigned[r] = true;
+            }
+            self.groups.push(group);
+            if self.groups.len() > self.best.len() {
+                self.best.clone_from(&self.groups);
+            }
+            if self.run() {
+                return true;
+            }
+            self.groups.pop();
+            for r in group {
+                self.assigned[r] = false;
+            }
+        }
+        false
+    }
+
+    /// Branch and bound search of the largest set of disjoint candidate groups: the rucksack
+    /// with the fewest available groups joins one of them or is left out. Returns `false` if the
+    /// budget ran out before the search completed
+    fn pack(&mut self) -> bool {
+        if self.budget == 0 {
+            return false;
+        }
+        self.budget -= 1;
+        if self.groups.len() > self.best.len() {
+            self.best.clone_from(&self.groups);
+        }
+        let available = |group: &[usize; 3]| {
+            group
+                .iter()
+                .all(|&r| !self.assigned[r] && !self.left_out[r])
+        };
+        let undecided: Vec<_> = (0..self.rucksacks.len())
+            .filter(|&r| !self.assigned[r] && !self.left_out[r])
+            .map(|r| {
+                (
+                    r,
+                    self.candidates[r].iter().filter(|g| available(g)).count(),
+                )
+            })
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        // Rucksacks without available group can't be grouped anymore
+        if self.groups.len() + undecided.len() / 3 <= self.best.len() {
+            return true;
+        }
+        let Some(&(rucksack, _)) = undecided.iter().min_by_key(|&&(_, count)| count) else {
+            return true;
+        };
+        let options: Vec<_> = self.candidates[rucksack]
+            .iter()
+            .copied()
+            .filter(available)
+            .collect();
+        for group in options {
+            for r in group {
+                self.assigned[r] = true;
+            }
+            self.groups.push(group);
+            let complete = self.pack();
+            self.groups.pop();
+            for r in group {
+                self.assigned[r] = false;
+            }
+            if !complete {
+                return false;
+            }
+        }
+        self.left_out[rucksack] = true;
+        let complete = self.pack();
+        self.left_out[rucksack] = false;
+        complete
+    }
+}
+
+/// Finds groups of three rucksacks, each sharing exactly one item type, covering every rucksack.
+///
+/// Candidate groups are enumerated with item set intersections, then assigned with a
+/// backtracking exact cover search. If no partition exists, a branch and bound search looks for
+/// the largest grouping, whose left out rucksacks are the fewest blocking a partition. That search
+/// gives up after [`PACKING_BUDGET`] nodes, the rucksacks left out of the largest grouping found
+/// then being reported as not minimal.
+pub fn discover(rucksacks: &[RuckSack]) -> Result<Discovery, String> {
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(format!(
+            "{} rucksacks can't be split in groups of three",
+            rucksacks.len()
+        ));
+    }
+    let items: Vec<_> = rucksacks.iter().map(RuckSack::all_items).collect();
+    let mut candidates = vec![Vec::new(); rucksacks.len()];
+    for a in 0..items.len() {
+        for b in a + 1..items.len() {
+            let shared = items[a].intersection(items[b]);
+            if shared.is_empty() {
+                continue;
+            }
+            for (c, &set) in items.iter().enumerate().skip(b + 1) {
+                if shared.intersection(set).len() == 1 {
+                    for r in [a, b, c] {
+                        candidates[r].push([a, b, c]);
+                    }
+                }
+            }
+        }
+    }
+    let mut search = Search {
+        candidates,
+        assigned: vec![false; rucksacks.len()],
+        left_out: vec![false; rucksacks.len()],
+        groups: Vec::new(),
+        best: Vec::new(),
+        budget: PACKING_BUDGET,
+        rucksacks,
+    };
+    // A rucksack without candidate group blocks any partition
+    if search.candidates.iter().all(|c| !c.is_empty()) && search.run() {
+        return Ok(Discovery::Partition(search.groups));
+    }
+    // The deepest grouping of the exact cover search is a first lower bound
+    let minimal = search.pack();
+    let mut covered = vec![false; rucksacks.len()];
+    search
+        .best
+        .iter()
+        .flatten()
+        .for_each(|&r| covered[r] = true);
+    Ok(Discovery::Blocked {
+        rucksacks: (0..rucksacks.len()).filter(|&r| !covered[r]).collect(),
+        minimal,
+    })
+}
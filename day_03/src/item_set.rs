@@ -0,0 +1,48 @@
+/// Set of item identifiers, one bit per item of a
+/// [`PriorityTable`](crate::priority::PriorityTable)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const fn insert(&mut self, item: u8) {
+        self.0 |= 1 << item;
+    }
+
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub const fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the items, lowest identifier first
+    pub fn iter(self) -> impl Iterator<Item = u8> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            let item = bits.trailing_zeros() as u8;
+            (bits != 0).then(|| {
+                bits &= bits - 1;
+                item
+            })
+        })
+    }
+}
+
+impl FromIterator<u8> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::default();
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
@@ -1,79 +1,153 @@
-use std::{collections::HashSet, str::FromStr};
+mod badges;
+mod bench;
+mod discovery;
+mod item_set;
+mod priority;
+mod rebalance;
+
+use std::{env, fs::read_to_string, str::FromStr};
+
+use badges::groups;
+use discovery::{discover, Discovery};
+use item_set::ItemSet;
+use priority::PriorityTable;
+use rebalance::rebalance;
 
 struct RuckSack {
-    items_left: HashSet<u8>,
-    items_right: HashSet<u8>,
+    items: Vec<u8>,
+    items_left: ItemSet,
+    items_right: ItemSet,
 }
 
 impl RuckSack {
-    pub fn common_items(&self) -> Vec<u8> {
-        let mut res = Vec::new();
-        for item in &self.items_left {
-            if self.items_right.contains(item) {
-                res.push(*item);
-            }
-        }
-        res
+    pub const fn common_items(&self) -> ItemSet {
+        self.items_left.intersection(self.items_right)
     }
 
-    pub fn all_items(&self) -> HashSet<u8> {
-        let mut res = self.items_right.clone();
-        res.extend(self.items_left.iter().copied());
-        res
+    pub const fn all_items(&self) -> ItemSet {
+        self.items_left.union(self.items_right)
     }
-}
 
-impl FromStr for RuckSack {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let chars: Vec<_> = s.chars().collect();
-        let (left, right) = chars.split_at(chars.len() / 2);
-
-        let value = |item: &char| {
-            if item.is_ascii_lowercase() {
-                Ok((*item as u8).saturating_sub(b'a') + 1)
-            } else if item.is_ascii_uppercase() {
-                Ok((*item as u8).saturating_sub(b'A') + 27)
-            } else {
-                Err(format!("{item} is not a valid item"))
-            }
-        };
+    /// Returns the items of the left and right compartments
+    pub fn compartments(&self) -> (&[u8], &[u8]) {
+        self.items.split_at(self.items.len() / 2)
+    }
 
-        let items_left = left.iter().map(value).collect::<Result<_, _>>()?;
-        let items_right = right.iter().map(value).collect::<Result<_, _>>()?;
+    pub fn parse(s: &str, table: &PriorityTable) -> Result<Self, String> {
+        let items: Vec<_> = s.chars().map(|c| table.id(c)).collect::<Result<_, _>>()?;
+        let (left, right) = items.split_at(items.len() / 2);
+        let items_left = left.iter().copied().collect();
+        let items_right = right.iter().copied().collect();
 
         Ok(Self {
+            items,
             items_left,
             items_right,
         })
     }
 }
 
+/// Prints the swaps fixing every rucksack, and the ones that can't be fixed
+fn print_rebalance(rucksacks: &[RuckSack], table: &PriorityTable) {
+    let mut unfixable = Vec::new();
+    let mut total = 0;
+    for (i, rucksack) in rucksacks.iter().enumerate() {
+        let Some(swaps) = rebalance(rucksack) else {
+            unfixable.push(i + 1);
+            continue;
+        };
+        let swaps: Vec<_> = swaps
+            .iter()
+            .map(|swap| format!("{}<->{}", table.item(swap.left), table.item(swap.right)))
+            .collect();
+        println!(
+            "Rucksack {}: {} swaps {}",
+            i + 1,
+            swaps.len(),
+            swaps.join(" ")
+        );
+        total += swaps.len();
+    }
+    println!("Total swaps: {total}");
+    println!("Rucksacks that can't be fixed: {unfixable:?}");
+}
+
+/// Prints the elf groups found among unordered rucksacks
+fn print_discovery(rucksacks: &[RuckSack]) {
+    match discover(rucksacks).unwrap() {
+        Discovery::Partition(groups) => {
+            for [a, b, c] in groups {
+                println!("Group: rucksacks {}, {}, {}", a + 1, b + 1, c + 1);
+            }
+        }
+        Discovery::Blocked { rucksacks, minimal } => {
+            let rucksacks: Vec<_> = rucksacks.iter().map(|r| r + 1).collect();
+            if minimal {
+                println!("Fewest rucksacks blocking a partition: {rucksacks:?}");
+            } else {
+                println!("No partition found, largest grouping found leaves out {rucksacks:?}");
+            }
+        }
+    }
+}
+
 fn main() {
+    // `day_03 [--group-size <size>] [--priorities <file>] [bench [<rucksack count>] | rebalance | discover]`
+    let mut group_size = 3;
+    let mut table = PriorityTable::default();
+    let mut params = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--group-size" => group_size = args.next().expect("Missing size").parse().unwrap(),
+            "--priorities" => {
+                let file = read_to_string(args.next().expect("Missing priorities")).unwrap();
+                table = PriorityTable::from_str(&file).unwrap();
+            }
+            _ => params.push(arg),
+        }
+    }
+    if let Some(("bench", params)) = params.split_first().map(|(c, p)| (c.as_str(), p)) {
+        let count = params.first().map_or(300_000, |c| c.parse().unwrap());
+        bench::run(count);
+        return;
+    }
     let file = include_str!("../input.txt");
     let rucksacks: Vec<_> = file
         .lines()
-        .map(|line| RuckSack::from_str(line).unwrap())
+        .map(|line| RuckSack::parse(line, &table).unwrap())
         .collect();
+    match params.first().map(String::as_str) {
+        Some("rebalance") => return print_rebalance(&rucksacks, &table),
+        Some("discover") => return print_discovery(&rucksacks),
+        Some(command) => panic!("Unknown command `{command}`"),
+        None => (),
+    }
     // Part 1
-    let common_items: Vec<_> = rucksacks.iter().flat_map(RuckSack::common_items).collect();
-    let sum: u32 = common_items.iter().copied().map(u32::from).sum();
-    println!("Part 1: Common items sum: {sum}");
-    // Part 2
     let sum: u32 = rucksacks
-        .chunks(3)
-        .map(|chunk| {
-            let [b_items, c_items] = [chunk[1].all_items(), chunk[2].all_items()];
-            u32::from(
-                chunk[0]
-                    .all_items()
-                    .iter()
-                    .copied()
-                    .find(|item| b_items.contains(item) && c_items.contains(item))
-                    .unwrap(),
-            )
-        })
+        .iter()
+        .flat_map(|r| r.common_items().iter())
+        .map(|id| table.priority(id))
         .sum();
-    println!("Part 2: Badges sum: {sum}");
+    println!("Part 1: Common items sum: {sum}");
+    // Part 2
+    let groups = match groups(&rucksacks, group_size) {
+        Ok(groups) => groups,
+        Err(e) => return println!("Part 2: {e}"),
+    };
+    let badges: Vec<_> = groups.iter().map(|group| group.badge(&table)).collect();
+    let errors: Vec<_> = badges.iter().filter_map(|b| b.as_ref().err()).collect();
+    if errors.is_empty() {
+        let sum: u32 = badges
+            .into_iter()
+            .flatten()
+            .map(|id| table.priority(id))
+            .sum();
+        println!("Part 2: Badges sum: {sum}");
+    } else {
+        println!("Part 2: {} invalid groups", errors.len());
+        for error in errors {
+            println!("  {error}");
+        }
+    }
 }
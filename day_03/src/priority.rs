@@ -0,0 +1,86 @@
+use std::{collections::HashMap, str::FromStr};
+
+/// Item alphabet, at most 64 items so any item set fits an [`ItemSet`](crate::item_set::ItemSet).
+///
+/// Every item is identified by its position in the table and worth a priority.
+#[derive(Debug, Clone)]
+pub struct PriorityTable {
+    items: Vec<(char, u32)>,
+    // ASCII items are looked up directly, as they are the most common
+    ascii_ids: [Option<u8>; 128],
+    ids: HashMap<char, u8>,
+}
+
+impl PriorityTable {
+    pub fn new(items: Vec<(char, u32)>) -> Result<Self, String> {
+        if items.len() > 64 {
+            return Err(format!("{} items don't fit in 64 bits", items.len()));
+        }
+        let mut ascii_ids = [None; 128];
+        let mut ids = HashMap::new();
+        for (id, &(item, _)) in items.iter().enumerate() {
+            let previous = match ascii_ids.get_mut(item as usize) {
+                Some(slot) => slot.replace(id as u8),
+                None => ids.insert(item, id as u8),
+            };
+            if previous.is_some() {
+                return Err(format!("{item} is defined twice"));
+            }
+        }
+        Ok(Self {
+            items,
+            ascii_ids,
+            ids,
+        })
+    }
+
+    /// Returns the item identifier
+    pub fn id(&self, item: char) -> Result<u8, String> {
+        match self.ascii_ids.get(item as usize) {
+            Some(id) => *id,
+            None => self.ids.get(&item).copied(),
+        }
+        .ok_or_else(|| format!("{item} is not a valid item"))
+    }
+
+    pub fn item(&self, id: u8) -> char {
+        self.items[usize::from(id)].0
+    }
+
+    pub fn priority(&self, id: u8) -> u32 {
+        self.items[usize::from(id)].1
+    }
+}
+
+impl Default for PriorityTable {
+    /// `a-z` are worth `1-26` and `A-Z` `27-52`
+    fn default() -> Self {
+        let items = ('a'..='z').chain('A'..='Z').zip(1..).collect();
+        Self::new(items).unwrap()
+    }
+}
+
+impl FromStr for PriorityTable {
+    type Err = String;
+
+    /// Parses one `<item> <priority>` pair per line
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let items = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (item, priority) = line
+                    .trim()
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| format!("{line} is not a valid `<item> <priority>` pair"))?;
+                let item = char::from_str(item).map_err(|_| format!("{item} is not an item"))?;
+                let priority = priority
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("{priority} is not a valid priority"))?;
+                Ok((item, priority))
+            })
+            .collect::<Result<_, String>>()?;
+        Self::new(items)
+    }
+}
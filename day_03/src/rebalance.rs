@@ -0,0 +1,76 @@
+use crate::RuckSack;
+
+/// An item of the left compartment exchanged with an item of the right one
+#[derive(Debug, Clone, Copy)]
+pub struct Swap {
+    pub left: u8,
+    pub right: u8,
+}
+
+fn relax(best: &mut Option<usize>, cost: usize) {
+    if best.is_none_or(|c| cost < c) {
+        *best = Some(cost);
+    }
+}
+
+/// Returns the fewest swaps after which no item type is in both compartments, or `None` if no
+/// arrangement of the items fits the compartment sizes.
+///
+/// Item types are assigned to compartments with a knapsack over their counts: the types kept on
+/// the left must exactly fill it, while bringing back as few items from the right as possible.
+pub fn rebalance(rucksack: &RuckSack) -> Option<Vec<Swap>> {
+    let (left, right) = rucksack.compartments();
+    let mut counts = [[0_usize; 2]; 64];
+    for &item in left {
+        counts[usize::from(item)][0] += 1;
+    }
+    for &item in right {
+        counts[usize::from(item)][1] += 1;
+    }
+    let types: Vec<_> = (0..64_u8)
+        .filter(|&t| counts[usize::from(t)] != [0, 0])
+        .collect();
+
+    // `best[i][size]` is the fewest items to bring left when the first `i` types fill `size`
+    let capacity = left.len();
+    let mut best = vec![vec![None; capacity + 1]; types.len() + 1];
+    best[0][0] = Some(0);
+    for (i, &t) in types.iter().enumerate() {
+        let [on_left, on_right] = counts[usize::from(t)];
+        for size in 0..=capacity {
+            let Some(cost) = best[i][size] else {
+                continue;
+            };
+            relax(&mut best[i + 1][size], cost);
+            if let Some(keep_left) = best[i + 1].get_mut(size + on_left + on_right) {
+                relax(keep_left, cost + on_right);
+            }
+        }
+    }
+    best[types.len()][capacity]?;
+
+    // Walk back the table to find which types stay on the left
+    let (mut to_left, mut to_right) = (Vec::new(), Vec::new());
+    let mut size = capacity;
+    for (i, &t) in types.iter().enumerate().rev() {
+        let [on_left, on_right] = counts[usize::from(t)];
+        let cost = best[i + 1][size];
+        let kept_left = size
+            .checked_sub(on_left + on_right)
+            .and_then(|previous| best[i][previous])
+            .is_some_and(|previous_cost| Some(previous_cost + on_right) == cost);
+        if kept_left {
+            to_left.extend(std::iter::repeat_n(t, on_right));
+            size -= on_left + on_right;
+        } else {
+            to_right.extend(std::iter::repeat_n(t, on_left));
+        }
+    }
+    Some(
+        to_right
+            .into_iter()
+            .zip(to_left)
+            .map(|(left, right)| Swap { left, right })
+            .collect(),
+    )
+}
@@ -0,0 +1,60 @@
+use crate::{depth::profile, interval_set::IntervalSet, Assignment};
+
+/// Sections cleaned by at least one elf, and by at least two elves
+pub fn coverage<'a>(
+    assignments: impl IntoIterator<Item = &'a Assignment>,
+) -> (IntervalSet, IntervalSet) {
+    let assignments: Vec<_> = assignments.into_iter().collect();
+    let covered = IntervalSet::new(assignments.iter().map(|a| (a.min, a.max)));
+    let covered_twice = IntervalSet::new(
+        profile(assignments)
+            .into_iter()
+            .filter(|run| run.depth >= 2)
+            .map(|run| (run.min, run.max)),
+    );
+    (covered, covered_twice)
+}
+
+/// Returns the elves whose every section is also cleaned by some other elf.
+///
+/// They may each be dropped on their own, but not necessarily all together: two identical
+/// assignments are both redundant.
+pub fn redundant(assignments: &[Assignment]) -> Vec<usize> {
+    let (_, covered_twice) = coverage(assignments);
+    assignments
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| covered_twice.contains(a.min, a.max))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Returns a smallest set of elves still cleaning every assigned section, sorted.
+///
+/// Sections are covered from left to right, always picking the elf reaching the furthest among
+/// the ones starting on the first uncovered section, which is optimal for intervals.
+pub fn minimal_cover(assignments: &[Assignment]) -> Vec<usize> {
+    let mut order: Vec<_> = (0..assignments.len()).collect();
+    order.sort_unstable_by_key(|&i| assignments[i].min);
+    let mut cover = Vec::new();
+    let mut candidates = order.into_iter().peekable();
+    // First section not covered yet by the chosen elves
+    let mut next = 0;
+    while let Some(&first) = candidates.peek() {
+        // Skips the gap up to the next assigned section
+        next = next.max(assignments[first].min);
+        let mut best: Option<usize> = None;
+        while let Some(i) = candidates.next_if(|&i| assignments[i].min <= next) {
+            if best.is_none_or(|b| assignments[i].max > assignments[b].max) {
+                best = Some(i);
+            }
+        }
+        let best = best.unwrap();
+        if assignments[best].max >= next {
+            cover.push(best);
+            next = assignments[best].max + 1;
+        }
+    }
+    cover.sort_unstable();
+    cover
+}
@@ -0,0 +1,55 @@
+use crate::Assignment;
+
+/// Consecutive sections cleaned by the same number of elves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub min: u32,
+    pub max: u32,
+    pub depth: usize,
+}
+
+/// Returns how many elves clean every section, run-length encoded from the first assigned
+/// section to the last one. Sections nobody cleans in between are runs of depth 0.
+///
+/// Only assignment ends are visited, so wide assignments cost no more than narrow ones.
+pub fn profile<'a>(assignments: impl IntoIterator<Item = &'a Assignment>) -> Vec<Run> {
+    // Depth changes at the first section of an assignment and right after its last one
+    let mut events: Vec<(u64, isize)> = assignments
+        .into_iter()
+        .flat_map(|a| [(u64::from(a.min), 1), (u64::from(a.max) + 1, -1)])
+        .collect();
+    events.sort_unstable();
+    let mut runs: Vec<Run> = Vec::new();
+    let mut depth = 0_isize;
+    let mut events = events.into_iter().peekable();
+    while let Some((section, change)) = events.next() {
+        depth += change;
+        while let Some((_, change)) = events.next_if(|&(s, _)| s == section) {
+            depth += change;
+        }
+        let Some(&(next, _)) = events.peek() else {
+            break;
+        };
+        let run = Run {
+            min: section as u32,
+            max: (next - 1) as u32,
+            depth: depth as usize,
+        };
+        match runs.last_mut() {
+            Some(last) if last.depth == run.depth => last.max = run.max,
+            _ => runs.push(run),
+        }
+    }
+    runs
+}
+
+/// Returns the highest depth of a profile, and the runs reaching it
+pub fn deepest(profile: &[Run]) -> (usize, Vec<Run>) {
+    let max = profile.iter().map(|run| run.depth).max().unwrap_or(0);
+    let runs = profile
+        .iter()
+        .filter(|run| run.depth == max)
+        .copied()
+        .collect();
+    (max, runs)
+}
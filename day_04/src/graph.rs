@@ -0,0 +1,149 @@
+use crate::{Assignment, Elf};
+
+/// Graph linking every pair of elves with overlapping assignments.
+///
+/// Containments are overlaps too, so they are edges as well.
+#[derive(Debug, Clone)]
+pub struct OverlapGraph {
+    pub elves: Vec<Elf>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl OverlapGraph {
+    pub fn new(elves: Vec<Elf>) -> Self {
+        let mut neighbours = vec![Vec::new(); elves.len()];
+        for (i, a) in elves.iter().enumerate() {
+            for (j, b) in elves.iter().enumerate().skip(i + 1) {
+                if a.assignment.overlaps(&b.assignment) {
+                    neighbours[i].push(j);
+                    neighbours[j].push(i);
+                }
+            }
+        }
+        Self { elves, neighbours }
+    }
+
+    /// Number of edges, and how many of them are containments
+    pub fn edge_count(&self) -> (usize, usize) {
+        let mut overlaps = 0;
+        let mut containments = 0;
+        for (i, neighbours) in self.neighbours.iter().enumerate() {
+            for &j in neighbours.iter().filter(|&&j| j > i) {
+                overlaps += 1;
+                let (a, b) = (&self.elves[i].assignment, &self.elves[j].assignment);
+                if a.contains(b) || b.contains(a) {
+                    containments += 1;
+                }
+            }
+        }
+        (overlaps, containments)
+    }
+
+    /// Returns the connected components, as sorted elf indexes
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.elves.len()];
+        let mut components = Vec::new();
+        for start in 0..self.elves.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut component = vec![start];
+            let mut next = 0;
+            while let Some(&elf) = component.get(next) {
+                next += 1;
+                for &neighbour in &self.neighbours[elf] {
+                    if !visited[neighbour] {
+                        visited[neighbour] = true;
+                        component.push(neighbour);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Returns the maximal cliques of mutually overlapping elves, with the sections they share.
+    ///
+    /// Intervals overlapping pairwise always share a section, so sweeping the assignment ends
+    /// finds every maximal clique: it is the set of open assignments whenever an assignment ends
+    /// right after another one started.
+    pub fn maximal_cliques(&self) -> Vec<(Vec<usize>, Assignment)> {
+        // Starts are sorted before ends of the same section, as they overlap
+        let mut events: Vec<_> = self
+            .elves
+            .iter()
+            .enumerate()
+            .flat_map(|(i, elf)| {
+                [
+                    (elf.assignment.min, false, i),
+                    (elf.assignment.max, true, i),
+                ]
+            })
+            .collect();
+        events.sort_unstable();
+        let mut open: Vec<usize> = Vec::new();
+        let mut cliques = Vec::new();
+        let mut started = false;
+        for (_, is_end, elf) in events {
+            if !is_end {
+                open.push(elf);
+                started = true;
+                continue;
+            }
+            if started {
+                let mut clique = open.clone();
+                clique.sort_unstable();
+                let shared = Assignment {
+                    min: clique
+                        .iter()
+                        .map(|&i| self.elves[i].assignment.min)
+                        .max()
+                        .unwrap(),
+                    max: clique
+                        .iter()
+                        .map(|&i| self.elves[i].assignment.max)
+                        .min()
+                        .unwrap(),
+                };
+                cliques.push((clique, shared));
+                started = false;
+            }
+            open.retain(|&i| i != elf);
+        }
+        cliques
+    }
+
+    /// Returns a longest chain of nested assignments, widest first.
+    ///
+    /// Elves with identical assignments contain each other, so they can all be part of the chain.
+    pub fn longest_containment_chain(&self) -> Vec<usize> {
+        // Containers come before the assignments they contain in that order
+        let mut order: Vec<_> = (0..self.elves.len()).collect();
+        order.sort_by_key(|&i| {
+            let assignment = self.elves[i].assignment;
+            (assignment.min, std::cmp::Reverse(assignment.max))
+        });
+        let mut lengths = vec![1; order.len()];
+        let mut previous = vec![None; order.len()];
+        for i in 0..order.len() {
+            let inner = self.elves[order[i]].assignment;
+            for j in 0..i {
+                if self.elves[order[j]].assignment.contains(&inner) && lengths[j] + 1 > lengths[i] {
+                    lengths[i] = lengths[j] + 1;
+                    previous[i] = Some(j);
+                }
+            }
+        }
+        let mut chain = Vec::new();
+        let mut current = (0..order.len()).max_by_key(|&i| lengths[i]);
+        while let Some(i) = current {
+            chain.push(order[i]);
+            current = previous[i];
+        }
+        chain.reverse();
+        chain
+    }
+}
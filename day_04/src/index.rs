@@ -0,0 +1,75 @@
+use crate::Assignment;
+
+/// Static interval tree answering which elves clean some sections.
+///
+/// Assignments are sorted by first section and seen as an implicit balanced tree, the middle
+/// entry of every slice being the root of its subtree. Each entry also stores the furthest
+/// section of its subtree, so subtrees ending before a query are skipped.
+#[derive(Debug, Clone)]
+pub struct IntervalTree {
+    /// `(assignment, elf)` sorted by assignment
+    entries: Vec<(Assignment, usize)>,
+    /// Furthest section of the subtree rooted at every entry
+    max_ends: Vec<u32>,
+}
+
+impl IntervalTree {
+    pub fn new(assignments: &[Assignment]) -> Self {
+        let mut entries: Vec<_> = assignments.iter().copied().zip(0..).collect();
+        entries.sort_unstable_by_key(|(a, elf)| (a.min, a.max, *elf));
+        let mut tree = Self {
+            max_ends: vec![0; entries.len()],
+            entries,
+        };
+        tree.build(0, tree.entries.len());
+        tree
+    }
+
+    /// Fills `max_ends` for the subtree over `start..end`, returning its furthest section
+    fn build(&mut self, start: usize, end: usize) -> u32 {
+        if start >= end {
+            return 0;
+        }
+        let mid = start + (end - start) / 2;
+        let max_end = self.entries[mid]
+            .0
+            .max
+            .max(self.build(start, mid))
+            .max(self.build(mid + 1, end));
+        self.max_ends[mid] = max_end;
+        max_end
+    }
+
+    /// Returns the elves cleaning `section`, sorted
+    pub fn point(&self, section: u32) -> Vec<usize> {
+        self.range(section, section)
+    }
+
+    /// Returns the elves cleaning at least one section of `min..=max`, sorted
+    pub fn range(&self, min: u32, max: u32) -> Vec<usize> {
+        let mut elves = Vec::new();
+        self.collect(0, self.entries.len(), min, max, &mut elves);
+        elves.sort_unstable();
+        elves
+    }
+
+    fn collect(&self, start: usize, end: usize, min: u32, max: u32, elves: &mut Vec<usize>) {
+        if start >= end {
+            return;
+        }
+        let mid = start + (end - start) / 2;
+        if self.max_ends[mid] < min {
+            return;
+        }
+        self.collect(start, mid, min, max, elves);
+        let (assignment, elf) = self.entries[mid];
+        // Entries on the right start after this one, so after the query too
+        if assignment.min > max {
+            return;
+        }
+        if assignment.max >= min {
+            elves.push(elf);
+        }
+        self.collect(mid + 1, end, min, max, elves);
+    }
+}
@@ -0,0 +1,95 @@
+/// Set of sections, stored as sorted inclusive ranges that neither overlap nor touch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<(u32, u32)>,
+}
+
+impl IntervalSet {
+    /// Builds a set from inclusive ranges, merging overlapping and adjacent ones.
+    /// Empty ranges (`min > max`) are ignored
+    pub fn new(ranges: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        let mut sorted: Vec<_> = ranges.into_iter().filter(|(min, max)| min <= max).collect();
+        sorted.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(sorted.len());
+        for (min, max) in sorted {
+            match merged.last_mut() {
+                Some(last) if min <= last.1.saturating_add(1) => last.1 = last.1.max(max),
+                _ => merged.push((min, max)),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[(u32, u32)] {
+        &self.ranges
+    }
+
+    /// Amount of sections in the set
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|(min, max)| u64::from(max - min) + 1)
+            .sum()
+    }
+
+    /// Whether every section from `min` to `max` is in the set
+    pub fn contains(&self, min: u32, max: u32) -> bool {
+        // Ranges don't touch, so the sections must all fall in the first one ending after `min`
+        let i = self.ranges.partition_point(|&(_, r_max)| r_max < min);
+        self.ranges
+            .get(i)
+            .is_some_and(|&(r_min, r_max)| r_min <= min && max <= r_max)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(self.ranges.iter().chain(&other.ranges).copied())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_min, a_max)), Some(&(b_min, b_max))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (min, max) = (a_min.max(b_min), a_max.min(b_max));
+            if min <= max {
+                ranges.push((min, max));
+            }
+            // The range ending first can't intersect anything else
+            if a_max < b_max {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut first = 0;
+        for &(min, max) in &self.ranges {
+            while other
+                .ranges
+                .get(first)
+                .is_some_and(|&(_, o_max)| o_max < min)
+            {
+                first += 1;
+            }
+            let mut start = Some(min);
+            for &(o_min, o_max) in &other.ranges[first..] {
+                let Some(current) = start.filter(|_| o_min <= max) else {
+                    break;
+                };
+                if o_min > current {
+                    ranges.push((current, o_min - 1));
+                }
+                start = (o_max < max).then(|| current.max(o_max + 1));
+            }
+            if let Some(start) = start {
+                ranges.push((start, max));
+            }
+        }
+        Self { ranges }
+    }
+}
@@ -1,6 +1,27 @@
-use std::str::FromStr;
+mod cover;
+mod depth;
+mod graph;
+mod index;
+mod interval_set;
+mod reassign;
+mod render;
 
-#[derive(Debug)]
+use std::{
+    env, fmt,
+    fs::read_to_string,
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+use cover::{coverage, minimal_cover, redundant};
+use depth::{deepest, profile};
+use graph::OverlapGraph;
+use index::IntervalTree;
+use interval_set::IntervalSet;
+use reassign::reassign;
+use render::Renderer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Assignment {
     min: u32,
     max: u32,
@@ -12,14 +33,47 @@ struct Pair {
     b: Assignment,
 }
 
+/// Every elf assigned on a line, in any number
+#[derive(Debug)]
+struct Crew {
+    assignments: Vec<Assignment>,
+}
+
+/// An elf of the input, identified by its 0-based line and position on that line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Elf {
+    line: usize,
+    position: usize,
+    assignment: Assignment,
+}
+
+impl Assignment {
+    pub const fn overlaps(&self, other: &Self) -> bool {
+        self.max >= other.min && self.min <= other.max
+    }
+
+    pub const fn contains(&self, other: &Self) -> bool {
+        self.min <= other.min && self.max >= other.max
+    }
+}
+
+impl Crew {
+    /// Returns the crew as a [`Pair`] if it has exactly two elves
+    pub fn pair(&self) -> Option<Pair> {
+        match self.assignments.as_slice() {
+            &[a, b] => Some(Pair { a, b }),
+            _ => None,
+        }
+    }
+}
+
 impl Pair {
     pub const fn is_overlapping(&self) -> bool {
-        self.a.max >= self.b.min && self.a.min <= self.b.max
+        self.a.overlaps(&self.b)
     }
 
     pub const fn is_fully_overlapping(&self) -> bool {
-        (self.a.min <= self.b.min && self.a.max >= self.b.max)
-            || (self.a.min >= self.b.min && self.a.max <= self.b.max)
+        self.a.contains(&self.b) || self.b.contains(&self.a)
     }
 }
 
@@ -32,10 +86,69 @@ impl FromStr for Assignment {
             .ok_or_else(|| format!("{s} is not a valid assignment"))?;
         let min = a.parse::<u32>().map_err(|e| e.to_string())?;
         let max = b.parse::<u32>().map_err(|e| e.to_string())?;
+        if min > max {
+            return Err(format!(
+                "{s} is not a valid assignment, it ends before it starts"
+            ));
+        }
         Ok(Self { min, max })
     }
 }
 
+/// Lists the elves of every line
+fn elves(crews: &[Crew]) -> Vec<Elf> {
+    crews
+        .iter()
+        .enumerate()
+        .flat_map(|(line, crew)| {
+            crew.assignments
+                .iter()
+                .enumerate()
+                .map(move |(position, &assignment)| Elf {
+                    line,
+                    position,
+                    assignment,
+                })
+        })
+        .collect()
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} ({})",
+            self.line + 1,
+            self.position + 1,
+            self.assignment
+        )
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.min, self.max)
+    }
+}
+
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.a, self.b)
+    }
+}
+
+impl FromStr for Crew {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let assignments = s
+            .split(',')
+            .map(Assignment::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { assignments })
+    }
+}
+
 impl FromStr for Pair {
     type Err = String;
 
@@ -49,14 +162,232 @@ impl FromStr for Pair {
     }
 }
 
+/// Prints the elves covered by the others, and a smallest set of elves cleaning every section
+fn print_cover(crews: &[Crew]) {
+    let elves = elves(crews);
+    let assignments: Vec<_> = elves.iter().map(|elf| elf.assignment).collect();
+    let redundant = redundant(&assignments);
+    println!("Redundant elves: {}", redundant.len());
+    for i in redundant {
+        println!("  {}", elves[i]);
+    }
+    let cover = minimal_cover(&assignments);
+    println!("Minimal cover: {} elves", cover.len());
+    for i in cover {
+        println!("  {}", elves[i]);
+    }
+}
+
+/// Reassigns every pair without overlap, returning the pairs in the input format and the total
+/// shift. The output is parsed back to check it round trips without overlap
+fn reassignment(pairs: &[Pair]) -> Result<(String, u32), String> {
+    let camp = pairs
+        .iter()
+        .map(|p| p.a.max.max(p.b.max))
+        .max()
+        .unwrap_or(0);
+    let mut res = String::new();
+    let mut total = 0;
+    for pair in pairs {
+        let (pair, shift) = reassign(pair, camp)?;
+        res.push_str(&format!("{pair}\n"));
+        total += shift;
+    }
+    for line in res.lines() {
+        let pair = Pair::from_str(line)?;
+        if pair.is_overlapping() {
+            return Err(format!("Reassigned pair {line} still overlaps"));
+        }
+    }
+    Ok((res, total))
+}
+
+/// Draws every pair, `options` being `[--width <columns>] [--window <a-b>] [--markers <n>]`
+fn print_render(pairs: &[Pair], options: &[String]) {
+    let camp = pairs
+        .iter()
+        .map(|p| p.a.max.max(p.b.max))
+        .max()
+        .unwrap_or(1);
+    let mut renderer = Renderer {
+        window: Assignment { min: 1, max: camp },
+        width: 100,
+        markers: 10,
+    };
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let value = options.next().expect("Missing option value");
+        match option.as_str() {
+            "--width" => renderer.width = value.parse().unwrap(),
+            "--window" => renderer.window = Assignment::from_str(value).unwrap(),
+            "--markers" => renderer.markers = value.parse().unwrap(),
+            _ => panic!("Unknown option `{option}`"),
+        }
+    }
+    assert!(renderer.width > 0, "Width must be positive");
+    if renderer.scale() > 1 {
+        println!("{} sections per column", renderer.scale());
+    }
+    if let Some(ruler) = renderer.ruler() {
+        println!("{}", ruler.trim_end());
+    }
+    for pair in pairs {
+        println!("{}", renderer.pair(pair));
+    }
+}
+
+/// Prints where elves crowd, and how many elves clean every section
+fn print_depth(crews: &[Crew]) {
+    let profile = profile(crews.iter().flat_map(|crew| &crew.assignments));
+    let (max, runs) = deepest(&profile);
+    let sections: Vec<_> = runs
+        .iter()
+        .map(|run| format!("{}-{}", run.min, run.max))
+        .collect();
+    println!(
+        "Maximum depth: {max} elves on sections {}",
+        sections.join(", ")
+    );
+    let runs: Vec<_> = profile
+        .iter()
+        .map(|run| format!("{}-{}:{}", run.min, run.max, run.depth))
+        .collect();
+    println!("Profile: {}", runs.join(" "));
+}
+
+/// Answers queries read from stdin, one per line: a section (`42`) or a range (`10-20`)
+fn print_queries(crews: &[Crew]) {
+    let elves = elves(crews);
+    let assignments: Vec<_> = elves.iter().map(|elf| elf.assignment).collect();
+    let tree = IntervalTree::new(&assignments);
+    for line in io::stdin().lock().lines() {
+        let line = line.unwrap();
+        let query = line.trim();
+        if query.is_empty() {
+            continue;
+        }
+        let found = if query.contains('-') {
+            Assignment::from_str(query).map(|a| tree.range(a.min, a.max))
+        } else {
+            query
+                .parse()
+                .map(|section| tree.point(section))
+                .map_err(|e| format!("{query} is not a valid query: {e}"))
+        };
+        match found {
+            Ok(found) => {
+                let names: String = found.iter().map(|&i| format!(" {}", elves[i])).collect();
+                println!("{query}: {} elves{names}", found.len());
+            }
+            Err(e) => println!("{e}"),
+        }
+    }
+}
+
+/// Prints the overlap graph components, maximal cliques and longest containment chain
+fn print_graph(crews: &[Crew]) {
+    let graph = OverlapGraph::new(elves(crews));
+    let (overlaps, containments) = graph.edge_count();
+    println!(
+        "{} elves, {overlaps} overlaps including {containments} containments",
+        graph.elves.len()
+    );
+    let components = graph.components();
+    let largest = components.iter().map(Vec::len).max().unwrap_or(0);
+    println!(
+        "Connected components: {} (largest: {largest} elves)",
+        components.len()
+    );
+    let cliques = graph.maximal_cliques();
+    println!("Maximal cliques: {}", cliques.len());
+    for (clique, shared) in cliques {
+        println!("  {} elves sharing sections {shared}", clique.len());
+    }
+    let chain: Vec<_> = graph
+        .longest_containment_chain()
+        .into_iter()
+        .map(|i| graph.elves[i].to_string())
+        .collect();
+    println!(
+        "Longest containment chain: {} elves: {}",
+        chain.len(),
+        chain.join(" > ")
+    );
+}
+
 fn main() {
-    let file = include_str!("../input.txt");
-    let pairs: Vec<_> = file
+    // `day_04 [--roster <file>] [cover | depth | graph | query | reassign | render [<options>]]`
+    let mut roster = None;
+    let mut params = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--roster" => {
+                roster = Some(read_to_string(args.next().expect("Missing roster")).unwrap());
+            }
+            _ => params.push(arg),
+        }
+    }
+    let file = roster.as_deref().unwrap_or(include_str!("../input.txt"));
+    let crews: Vec<_> = file
         .lines()
-        .map(|line| Pair::from_str(line).unwrap())
+        .enumerate()
+        .map(|(i, line)| {
+            Crew::from_str(line)
+                .map_err(|e| format!("Line {}: {e}", i + 1))
+                .unwrap()
+        })
         .collect();
+    match params.first().map(String::as_str) {
+        Some("cover") => return print_cover(&crews),
+        Some("depth") => return print_depth(&crews),
+        Some("graph") => return print_graph(&crews),
+        Some("query") => return print_queries(&crews),
+        Some("reassign") => {
+            let pairs: Vec<_> = crews.iter().filter_map(Crew::pair).collect();
+            match reassignment(&pairs) {
+                Ok((res, total)) => {
+                    print!("{res}");
+                    eprintln!("Total shifted sections: {total}");
+                }
+                Err(e) => println!("{e}"),
+            }
+            return;
+        }
+        Some("render") => {
+            let pairs: Vec<_> = crews.iter().filter_map(Crew::pair).collect();
+            return print_render(&pairs, &params[1..]);
+        }
+        Some(command) => panic!("Unknown command `{command}`"),
+        None => (),
+    }
+    // Lines with another number of elves than two have no pair to compare
+    let pairs: Vec<_> = crews.iter().filter_map(Crew::pair).collect();
+    if pairs.len() < crews.len() {
+        println!(
+            "{} lines without exactly two elves are left out of parts 1 and 2",
+            crews.len() - pairs.len()
+        );
+    }
     let overlapped_pairs = pairs.iter().filter(|p| p.is_fully_overlapping()).count();
     println!("Part 1: {overlapped_pairs}");
     let overlapped_pairs = pairs.iter().filter(|p| p.is_overlapping()).count();
     println!("Part 2: {overlapped_pairs}");
+    let (cleaned, shared) = pairs.iter().fold((0, 0), |(cleaned, shared), p| {
+        let a = IntervalSet::new([(p.a.min, p.a.max)]);
+        let b = IntervalSet::new([(p.b.min, p.b.max)]);
+        (
+            cleaned + a.union(&b).len(),
+            shared + a.intersection(&b).len(),
+        )
+    });
+    println!("Pairs clean {cleaned} sections, {shared} of them twice");
+
+    let (covered, covered_twice) = coverage(crews.iter().flat_map(|c| &c.assignments));
+    let camp = IntervalSet::new([(1, covered.ranges().last().map_or(0, |r| r.1))]);
+    println!(
+        "Sections nobody cleans: {:?}",
+        camp.difference(&covered).ranges()
+    );
+    println!("Sections cleaned at least twice: {}", covered_twice.len());
 }
@@ -0,0 +1,95 @@
+use crate::{Assignment, Pair};
+
+impl Assignment {
+    const fn len(self) -> u32 {
+        self.max - self.min + 1
+    }
+
+    /// Sections the elf has to move by to go from `self` to `other`
+    const fn shift_to(self, other: Self) -> u32 {
+        let (min, max) = (self.min.abs_diff(other.min), self.max.abs_diff(other.max));
+        if min > max {
+            min
+        } else {
+            max
+        }
+    }
+}
+
+/// Moves `left` to the left and `right` to the right until they no longer overlap, keeping their
+/// lengths and staying within `1..=camp`.
+///
+/// Any split of the moves costs the same, the most even one is chosen.
+fn place(left: Assignment, right: Assignment, camp: u32) -> Option<(Assignment, Assignment)> {
+    let gap = (i64::from(left.max) + 1 - i64::from(right.min)).max(0);
+    let room_left = i64::from(left.min) - 1;
+    let room_right = i64::from(camp) - i64::from(right.max);
+    if room_left < 0 || room_right < 0 || room_left + room_right < gap {
+        return None;
+    }
+    let to_left = (gap / 2).clamp((gap - room_right).max(0), room_left.min(gap)) as u32;
+    let to_right = gap as u32 - to_left;
+    Some((
+        Assignment {
+            min: left.min - to_left,
+            max: left.max - to_left,
+        },
+        Assignment {
+            min: right.min + to_right,
+            max: right.max + to_right,
+        },
+    ))
+}
+
+/// Splits the camp between the two elves, shortening the longest assignment first
+fn tile(left: Assignment, right: Assignment, camp: u32) -> (Assignment, Assignment) {
+    let excess = left.len() + right.len() - camp;
+    let (mut left_len, mut right_len) = (left.len(), right.len());
+    let (longest, shortest) = if left_len >= right_len {
+        (&mut left_len, &mut right_len)
+    } else {
+        (&mut right_len, &mut left_len)
+    };
+    let cut = excess.min(*longest - *shortest);
+    *longest -= cut + (excess - cut).div_ceil(2);
+    *shortest -= (excess - cut) / 2;
+    (
+        Assignment {
+            min: 1,
+            max: left_len,
+        },
+        Assignment {
+            min: left_len + 1,
+            max: camp,
+        },
+    )
+}
+
+/// Returns a new pair without overlap within sections `1..=camp`, and the sections the elves
+/// were shifted by in total.
+///
+/// Lengths are kept whenever both elves fit in the camp, and the shift is then minimal. Otherwise
+/// the elves share the whole camp.
+pub fn reassign(pair: &Pair, camp: u32) -> Result<(Pair, u32), String> {
+    let (a, b) = (pair.a, pair.b);
+    if a.min == 0 || b.min == 0 || a.max.max(b.max) > camp {
+        return Err(format!("{pair} does not fit in sections 1-{camp}"));
+    }
+    if camp < 2 {
+        return Err(format!("Sections 1-{camp} can't be shared by two elves"));
+    }
+    let fits = u64::from(a.len()) + u64::from(b.len()) <= u64::from(camp);
+    let candidates = if fits {
+        [place(a, b, camp), place(b, a, camp).map(|(b, a)| (a, b))]
+    } else {
+        let (b_first, a_last) = tile(b, a, camp);
+        [Some(tile(a, b, camp)), Some((a_last, b_first))]
+    };
+    let (a, b) = candidates
+        .into_iter()
+        .flatten()
+        .min_by_key(|&(new_a, new_b)| pair.a.shift_to(new_a) + pair.b.shift_to(new_b))
+        .unwrap();
+    let shift = pair.a.shift_to(a) + pair.b.shift_to(b);
+    Ok((Pair { a, b }, shift))
+}
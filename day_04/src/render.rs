@@ -0,0 +1,94 @@
+use crate::{Assignment, Pair};
+
+/// Draws assignments in the puzzle format, `.234.....` for `2-4`.
+///
+/// Only the sections of `window` are drawn. When they don't fit in `width` columns, every column
+/// stands for several sections and shows `#` when the elf cleans all of them, `+` when it cleans
+/// some of them.
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    pub window: Assignment,
+    pub width: u32,
+    /// Marks the columns holding a multiple of this section on a ruler line, `0` draws no ruler
+    pub markers: u32,
+}
+
+impl Renderer {
+    /// Sections drawn in every column
+    pub const fn scale(&self) -> u32 {
+        let sections = self.window.max - self.window.min + 1;
+        let scale = sections.div_ceil(self.width);
+        if scale > 1 {
+            scale
+        } else {
+            1
+        }
+    }
+
+    /// First and last section of every column
+    fn columns(&self) -> impl Iterator<Item = (u32, u32)> {
+        let (window, scale) = (self.window, self.scale());
+        (window.min..=window.max)
+            .step_by(scale as usize)
+            .map(move |min| (min, min.saturating_add(scale - 1).min(window.max)))
+    }
+
+    /// Draws the columns of the sections of `sections`
+    fn draw(&self, sections: Option<Assignment>) -> String {
+        let scale = self.scale();
+        self.columns()
+            .map(|(min, max)| {
+                let Some(s) = sections.filter(|s| s.min <= max && s.max >= min) else {
+                    return '.';
+                };
+                if scale == 1 {
+                    char::from(b'0' + (min % 10) as u8)
+                } else if s.min <= min && s.max >= max {
+                    '#'
+                } else {
+                    '+'
+                }
+            })
+            .collect()
+    }
+
+    pub fn ruler(&self) -> Option<String> {
+        if self.markers == 0 {
+            return None;
+        }
+        let ruler = self
+            .columns()
+            .map(|(min, max)| {
+                if max / self.markers * self.markers >= min {
+                    '|'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        Some(ruler)
+    }
+
+    pub fn assignment(&self, assignment: Assignment) -> String {
+        format!("{}  {assignment}", self.draw(Some(assignment)))
+    }
+
+    /// Draws both elves of `pair`, then `^` under the sections they both clean
+    pub fn pair(&self, pair: &Pair) -> String {
+        let overlap = Assignment {
+            min: pair.a.min.max(pair.b.min),
+            max: pair.a.max.min(pair.b.max),
+        };
+        let overlap = (overlap.min <= overlap.max).then_some(overlap);
+        let highlight = self
+            .draw(overlap)
+            .replace(|c| c != '.', "^")
+            .replace('.', " ");
+        format!(
+            "{}\n{}\n{}",
+            self.assignment(pair.a),
+            self.assignment(pair.b),
+            highlight.trim_end()
+        )
+    }
+}
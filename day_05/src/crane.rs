@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+use crate::Stacks;
+
+/// Warehouse equipment moving crates between stacks
+pub trait Crane {
+    /// Moves `amount` crates from the stack `from` to the stack `to`.
+    /// Both 0-based indexes are valid and the `from` stack holds enough crates
+    fn move_crates(&self, stacks: &mut Stacks, amount: usize, from: usize, to: usize);
+}
+
+/// Moves crates one at a time, reversing their order
+#[derive(Debug, Clone, Copy)]
+pub struct CrateMover9000;
+
+/// Moves all crates at once, keeping their order
+#[derive(Debug, Clone, Copy)]
+pub struct CrateMover9001;
+
+/// Moves at most `capacity` crates at once, keeping the order of every lift
+#[derive(Debug, Clone, Copy)]
+pub struct CapacityLimited {
+    capacity: usize,
+}
+
+/// Only reaches the stacks next to each other, so crates go through every stack in between
+#[derive(Debug, Clone, Copy)]
+pub struct AdjacentOnly<C> {
+    crane: C,
+}
+
+impl CapacityLimited {
+    pub fn new(capacity: usize) -> Result<Self, String> {
+        if capacity == 0 {
+            return Err("A crane must lift at least one crate".to_string());
+        }
+        Ok(Self { capacity })
+    }
+}
+
+impl<C: Crane> AdjacentOnly<C> {
+    pub const fn new(crane: C) -> Self {
+        Self { crane }
+    }
+}
+
+impl Crane for CrateMover9000 {
+    fn move_crates(&self, stacks: &mut Stacks, amount: usize, from: usize, to: usize) {
+        for _ in 0..amount {
+            stacks.lift(1, from, to);
+        }
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn move_crates(&self, stacks: &mut Stacks, amount: usize, from: usize, to: usize) {
+        stacks.lift(amount, from, to);
+    }
+}
+
+impl Crane for CapacityLimited {
+    fn move_crates(&self, stacks: &mut Stacks, amount: usize, from: usize, to: usize) {
+        let mut remaining = amount;
+        while remaining > 0 {
+            let lifted = remaining.min(self.capacity);
+            stacks.lift(lifted, from, to);
+            remaining -= lifted;
+        }
+    }
+}
+
+impl<C: Crane> Crane for AdjacentOnly<C> {
+    fn move_crates(&self, stacks: &mut Stacks, amount: usize, from: usize, to: usize) {
+        let mut current = from;
+        while current != to {
+            let next = if to > current {
+                current + 1
+            } else {
+                current - 1
+            };
+            self.crane.move_crates(stacks, amount, current, next);
+            current = next;
+        }
+    }
+}
+
+impl<C: Crane + ?Sized> Crane for Box<C> {
+    fn move_crates(&self, stacks: &mut Stacks, amount: usize, from: usize, to: usize) {
+        (**self).move_crates(stacks, amount, from, to);
+    }
+}
+
+/// Parses a crane model: `9000`, `9001`, `capacity-<crates>` or `adjacent-<model>`
+pub fn parse(s: &str) -> Result<Box<dyn Crane>, String> {
+    if let Some(model) = s.strip_prefix("adjacent-") {
+        return Ok(Box::new(AdjacentOnly::new(parse(model)?)));
+    }
+    match s {
+        "9000" => Ok(Box::new(CrateMover9000)),
+        "9001" => Ok(Box::new(CrateMover9001)),
+        _ => {
+            let capacity = s
+                .strip_prefix("capacity-")
+                .ok_or_else(|| format!("{s} is not a valid crane model"))?;
+            let capacity = usize::from_str(capacity).map_err(|e| format!("{s}: {e}"))?;
+            Ok(Box::new(CapacityLimited::new(capacity)?))
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::VecDeque, str::FromStr};
+mod crane;
+
+use std::{collections::VecDeque, env, str::FromStr};
+
+use crane::{Crane, CrateMover9000, CrateMover9001};
 
 type Crate = char;
 
@@ -20,29 +24,37 @@ struct MoveInstruction {
 }
 
 impl Stacks {
-    pub fn apply_9000(&mut self, instruction: &MoveInstruction) {
-        for _ in 0..instruction.amount {
-            let item = self.stacks[instruction.from_index - 1]
-                .crates
-                .pop_front()
-                .unwrap();
-            self.stacks[instruction.to_index - 1]
-                .crates
-                .push_front(item);
+    /// Moves the `amount` top crates of the stack `from` on top of the stack `to`, keeping their
+    /// order
+    pub fn lift(&mut self, amount: usize, from: usize, to: usize) {
+        let items: Vec<_> = self.stacks[from].crates.drain(..amount).rev().collect();
+        for item in items {
+            self.stacks[to].crates.push_front(item);
         }
     }
 
-    pub fn apply_9001(&mut self, instruction: &MoveInstruction) {
-        let items: Vec<_> = self.stacks[instruction.from_index - 1]
-            .crates
-            .drain(..instruction.amount)
-            .rev()
-            .collect();
-        for item in items {
-            self.stacks[instruction.to_index - 1]
-                .crates
-                .push_front(item);
+    /// Applies `instruction` with `crane`, failing if a stack doesn't exist or lacks crates
+    pub fn apply(
+        &mut self,
+        crane: &impl Crane,
+        instruction: &MoveInstruction,
+    ) -> Result<(), String> {
+        let stack_count = self.stacks.len();
+        let index = |i: usize| {
+            i.checked_sub(1)
+                .filter(|&i| i < stack_count)
+                .ok_or_else(|| format!("Stack {i} does not exist"))
+        };
+        let (from, to) = (index(instruction.from_index)?, index(instruction.to_index)?);
+        let available = self.stacks[from].crates.len();
+        if instruction.amount > available {
+            return Err(format!(
+                "Can't move {} crates from stack {}, it holds {available}",
+                instruction.amount, instruction.from_index
+            ));
         }
+        crane.move_crates(self, instruction.amount, from, to);
+        Ok(())
     }
 
     pub fn top_crates(&self) -> String {
@@ -93,7 +105,17 @@ impl FromStr for Stacks {
     }
 }
 
+/// Applies every instruction with `crane` and returns the top crates
+fn top_crates(stacks: &Stacks, instructions: &[MoveInstruction], crane: &impl Crane) -> String {
+    let mut stacks = stacks.clone();
+    for instruction in instructions {
+        stacks.apply(crane, instruction).unwrap();
+    }
+    stacks.top_crates()
+}
+
 fn main() {
+    // `day_05 [--crane <model>]...`
     let file = include_str!("../input.txt");
     let (stacks, instructions) = file.split_once("\n\n").unwrap();
     let instructions: Vec<_> = instructions
@@ -104,15 +126,22 @@ fn main() {
     let base_stacks = Stacks::from_str(stacks).unwrap();
 
     // Part 1
-    let mut stacks = base_stacks.clone();
-    for instruction in &instructions {
-        stacks.apply_9000(instruction);
-    }
-    println!("Part 1: Top crates {}", stacks.top_crates());
+    let top = top_crates(&base_stacks, &instructions, &CrateMover9000);
+    println!("Part 1: Top crates {top}");
     // Part 2
-    let mut stacks = base_stacks;
-    for instruction in &instructions {
-        stacks.apply_9001(instruction);
+    let top = top_crates(&base_stacks, &instructions, &CrateMover9001);
+    println!("Part 2: Top crates {top}");
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--crane" => {
+                let model = args.next().expect("Missing crane model");
+                let crane = crane::parse(&model).unwrap();
+                let top = top_crates(&base_stacks, &instructions, &crane);
+                println!("Top crates with crane {model}: {top}");
+            }
+            _ => panic!("Unknown argument `{arg}`"),
+        }
     }
-    println!("Part 2: Top crates {}", stacks.top_crates());
 }